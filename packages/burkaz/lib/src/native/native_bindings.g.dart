@@ -57,6 +57,12 @@ final class CBurkazSchemaFieldOptions extends Struct {
 
   @Size()
   external int indexingTokenizerLength;
+
+  @Uint8()
+  external int unique;
+
+  @Uint8()
+  external int datePrecision;
 }
 
 @Native<
@@ -294,10 +300,17 @@ external int burkaz_query_runner_delete_all(
   Pointer<CBurkazQueryRunner> queryRunnerPointer,
 );
 
-@Native<ResultCode Function(Pointer<CBurkazIndex>, Pointer<CBurkazObject>)>()
+@Native<
+  ResultCode Function(
+    Pointer<CBurkazIndex>,
+    Pointer<CBurkazObject>,
+    Pointer<Uint64>,
+  )
+>()
 external int burkaz_index_add(
   Pointer<CBurkazIndex> indexPointer,
   Pointer<CBurkazObject> objectPointer,
+  Pointer<Uint64> idPointer,
 );
 
 @Native<
@@ -305,12 +318,14 @@ external int burkaz_index_add(
     Pointer<CBurkazIndex>,
     Pointer<Pointer<CBurkazObject>>,
     Size,
+    Pointer<Uint64>,
   )
 >()
 external int burkaz_index_add_all(
   Pointer<CBurkazIndex> indexPointer,
   Pointer<Pointer<CBurkazObject>> objectArrayPointer,
   int objectArrayLength,
+  Pointer<Uint64> idArrayPointer,
 );
 
 @Native<
@@ -436,3 +451,40 @@ external Pointer<CBurkazTerm> burkaz_term_int(int fieldId, int value);
 
 @Native<Pointer<CBurkazTerm> Function(Uint32, Bool)>()
 external Pointer<CBurkazTerm> burkaz_term_boolean(int fieldId, bool value);
+
+final class CBurkazTransaction extends Opaque {}
+
+@Native<
+  ResultCode Function(
+    Pointer<CBurkazIndex>,
+    Pointer<Pointer<CBurkazTransaction>>,
+  )
+>()
+external int burkaz_index_begin_transaction(
+  Pointer<CBurkazIndex> indexPointer,
+  Pointer<Pointer<CBurkazTransaction>> transactionPointerPointer,
+);
+
+@Native<
+  ResultCode Function(
+    Pointer<CBurkazTransaction>,
+    Pointer<Pointer<Uint64>>,
+    Pointer<Size>,
+  )
+>()
+external int burkaz_transaction_commit(
+  Pointer<CBurkazTransaction> transactionPointer,
+  Pointer<Pointer<Uint64>> idArrayPointer,
+  Pointer<Size> idArrayLengthPointer,
+);
+
+@Native<Void Function(Pointer<Uint64>, Size)>()
+external void burkaz_free_transaction_ids(
+  Pointer<Uint64> idArrayPointer,
+  int idArrayLength,
+);
+
+@Native<Void Function(Pointer<CBurkazTransaction>)>()
+external void burkaz_transaction_rollback(
+  Pointer<CBurkazTransaction> transactionPointer,
+);
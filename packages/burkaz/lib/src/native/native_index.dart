@@ -72,7 +72,7 @@ class NativeBurkazIndex<T> extends BurkazIndex<T> {
   @override
   void add(T object) {
     final nativeObject = objectToNative(object);
-    burkaz_index_add(ptr, nativeObject).checkError();
+    burkaz_index_add(ptr, nativeObject, nullptr).checkError();
   }
 
   @override
@@ -96,6 +96,7 @@ class NativeBurkazIndex<T> extends BurkazIndex<T> {
         ptr,
         objectArrayPointer,
         objectList.length,
+        nullptr,
       ).checkError();
     });
   }
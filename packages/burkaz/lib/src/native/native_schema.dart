@@ -43,6 +43,8 @@ extension FieldOptionsToNativeExtension on FieldOptions {
     if (isIndexed) optionsPointer.ref.indexed = 1;
     if (fieldnorms) optionsPointer.ref.fieldnorms = 1;
     if (isFast) optionsPointer.ref.fast = 1;
+    optionsPointer.ref.unique = isUnique ? 1 : 0;
+    optionsPointer.ref.datePrecision = 0;
     if (indexingStrategy case final strategy?) {
       optionsPointer.ref.indexingStrategy = strategy.code;
     }
@@ -28,6 +28,9 @@ abstract class FieldOptions {
 
   /// Whether the field has field norms.
   bool get fieldnorms => isIndexed;
+
+  /// Whether the field is the unique key used by upserts and deletes by key.
+  bool get isUnique => false;
 }
 
 /// The options for a numeric field in a schema.
@@ -99,10 +102,12 @@ class TextFieldOptions extends FieldOptions {
     FastTextFieldOptions fast = const FastTextFieldOptions.disabled(),
     bool stored = false,
     bool coerce = false,
+    bool unique = false,
   }) : _indexed = indexed,
        _stored = stored,
        _fast = fast,
-       _coerce = coerce;
+       _coerce = coerce,
+       _unique = unique;
 
   @override
   final ValueType type = ValueType.text;
@@ -133,6 +138,10 @@ class TextFieldOptions extends FieldOptions {
   final bool _coerce;
   @override
   bool get shouldCoerce => _coerce;
+
+  final bool _unique;
+  @override
+  bool get isUnique => _unique;
 }
 
 /// The options for a fast text field in a schema.
@@ -1,5 +1,5 @@
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct BurkazObjectAddr(u64);
 
 impl std::fmt::Display for BurkazObjectAddr {
@@ -47,3 +47,20 @@ impl Into<tantivy::DocAddress> for BurkazObjectAddr {
         tantivy::DocAddress::new(self.segment_ord(), self.doc_id())
     }
 }
+
+/// An object address paired with the relevance score it was found with.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct BurkazScoredObjectAddr {
+    pub addr: BurkazObjectAddr,
+    pub score: tantivy::Score,
+}
+
+impl From<(tantivy::Score, tantivy::DocAddress)> for BurkazScoredObjectAddr {
+    fn from((score, addr): (tantivy::Score, tantivy::DocAddress)) -> Self {
+        Self {
+            addr: addr.into(),
+            score,
+        }
+    }
+}
@@ -0,0 +1,865 @@
+use tantivy::{
+    aggregation::{
+        Key,
+        agg_req::{Aggregation, AggregationVariants, Aggregations},
+        agg_result::{
+            AggregationResult, AggregationResults, BucketEntries, BucketResult, MetricResult,
+        },
+        bucket::{
+            DateHistogramAggregationReq, HistogramAggregation, HistogramBounds, TermsAggregation,
+        },
+        metric::{PercentileValues, PercentilesAggregationReq, StatsAggregation},
+    },
+    schema::{FieldEntry, FieldType, Schema},
+};
+
+use crate::{error::BurkazError, query_runner::field_entry};
+
+const TERMS: &str = "terms";
+const STATS: &str = "stats";
+const PERCENTILES: &str = "percentiles";
+const HISTOGRAM: &str = "histogram";
+
+/// Date values cross the FFI boundary in microseconds, while tantivy buckets
+/// dates in milliseconds.
+const MICROS_PER_MILLI: f64 = 1_000.0;
+
+fn aggregation(agg: AggregationVariants) -> Aggregation {
+    Aggregation {
+        agg,
+        sub_aggregation: Default::default(),
+    }
+}
+
+fn take_result(results: &mut AggregationResults, name: &str) -> crate::Result<AggregationResult> {
+    results
+        .0
+        .remove(name)
+        .ok_or_else(|| BurkazError::UnknownError(format!("{name} aggregation returned no result")))
+}
+
+fn unexpected_result(name: &str) -> BurkazError {
+    BurkazError::UnknownError(format!("{name} aggregation returned an unexpected result"))
+}
+
+/// An aggregation computed over the objects matched by a query, or over the
+/// objects of a bucket when it is nested in a bucket aggregation.
+#[derive(Debug, Clone)]
+pub enum BurkazAggregation {
+    Terms(BurkazTermsAggregation),
+    Metrics(BurkazMetricsAggregation),
+    Histogram(BurkazHistogramAggregation),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BurkazAggregationResult {
+    Terms(BurkazTermsAggregationResult),
+    Metrics(BurkazMetricsAggregationResult),
+    Histogram(BurkazHistogramAggregationResult),
+}
+
+impl BurkazAggregation {
+    /// Adds the tantivy aggregations computing this one to `aggregations`, naming
+    /// them with `prefix` so several aggregations can share the same level.
+    pub(crate) fn to_tantivy_aggregations(
+        &self,
+        schema: &Schema,
+        prefix: &str,
+        aggregations: &mut Aggregations,
+    ) -> crate::Result<()> {
+        match self {
+            BurkazAggregation::Terms(terms) => {
+                terms.to_tantivy_aggregations(schema, prefix, aggregations)
+            }
+            BurkazAggregation::Metrics(metrics) => {
+                metrics.to_tantivy_aggregations(schema, prefix, aggregations)
+            }
+            BurkazAggregation::Histogram(histogram) => {
+                histogram.to_tantivy_aggregations(schema, prefix, aggregations)
+            }
+        }
+    }
+
+    pub(crate) fn read_tantivy_results(
+        &self,
+        schema: &Schema,
+        prefix: &str,
+        results: &mut AggregationResults,
+    ) -> crate::Result<BurkazAggregationResult> {
+        Ok(match self {
+            BurkazAggregation::Terms(terms) => BurkazAggregationResult::Terms(
+                BurkazTermsAggregationResult::from_tantivy_results(terms, schema, prefix, results)?,
+            ),
+            BurkazAggregation::Metrics(metrics) => BurkazAggregationResult::Metrics(
+                BurkazMetricsAggregationResult::from_tantivy_results(metrics, prefix, results)?,
+            ),
+            BurkazAggregation::Histogram(histogram) => BurkazAggregationResult::Histogram(
+                BurkazHistogramAggregationResult::from_tantivy_results(
+                    histogram, schema, prefix, results,
+                )?,
+            ),
+        })
+    }
+}
+
+/// Buckets the matching objects by the values of a fast field.
+#[derive(Debug, Clone)]
+pub struct BurkazTermsAggregation {
+    pub field_id: u32,
+    /// Number of buckets to return, the most frequent values come first.
+    pub size: u32,
+    /// Values found in fewer objects are left out.
+    pub min_doc_count: u64,
+}
+
+impl Default for BurkazTermsAggregation {
+    fn default() -> Self {
+        Self {
+            field_id: 0,
+            size: 10,
+            min_doc_count: 1,
+        }
+    }
+}
+
+impl BurkazTermsAggregation {
+    pub(crate) fn to_tantivy_aggregations(
+        &self,
+        schema: &Schema,
+        prefix: &str,
+        aggregations: &mut Aggregations,
+    ) -> crate::Result<()> {
+        let entry = field_entry(schema, self.field_id)?;
+        if !matches!(
+            entry.field_type(),
+            FieldType::Str(_)
+                | FieldType::I64(_)
+                | FieldType::U64(_)
+                | FieldType::F64(_)
+                | FieldType::Bool(_)
+        ) {
+            return Err(BurkazError::InvalidField(format!(
+                "cannot aggregate terms of field `{}`, only text, numeric and boolean fields are supported",
+                entry.name()
+            )));
+        }
+
+        if !entry.is_fast() {
+            return Err(BurkazError::InvalidField(format!(
+                "cannot aggregate terms of field `{}`, it is not declared as fast",
+                entry.name()
+            )));
+        }
+
+        let terms = AggregationVariants::Terms(TermsAggregation {
+            field: entry.name().to_owned(),
+            size: Some(self.size),
+            min_doc_count: Some(self.min_doc_count),
+            ..Default::default()
+        });
+        aggregations.insert(format!("{prefix}{TERMS}"), aggregation(terms));
+
+        Ok(())
+    }
+}
+
+/// The value shared by the objects of a bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BurkazBucketKey {
+    Int64(i64),
+    Text(String),
+    Boolean(bool),
+    F64(f64),
+    U64(u64),
+}
+
+impl BurkazBucketKey {
+    /// Tantivy reports booleans as `0` and `1`, so the key is read back with the
+    /// type of the aggregated field.
+    fn from_tantivy_key(key: Key, entry: &FieldEntry) -> Self {
+        match (key, entry.field_type()) {
+            (Key::Str(value), _) => BurkazBucketKey::Text(value),
+            (Key::U64(value), FieldType::Bool(_)) => BurkazBucketKey::Boolean(value != 0),
+            (Key::I64(value), _) => BurkazBucketKey::Int64(value),
+            (Key::U64(value), FieldType::I64(_)) => BurkazBucketKey::Int64(value as i64),
+            (Key::U64(value), _) => BurkazBucketKey::U64(value),
+            (Key::F64(value), FieldType::I64(_)) => BurkazBucketKey::Int64(value as i64),
+            (Key::F64(value), _) => BurkazBucketKey::F64(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BurkazTermsBucket {
+    pub key: BurkazBucketKey,
+    pub doc_count: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BurkazTermsAggregationResult {
+    pub buckets: Vec<BurkazTermsBucket>,
+    /// Number of objects whose value did not make it into the returned buckets.
+    pub sum_other_doc_count: u64,
+}
+
+impl BurkazTermsAggregationResult {
+    pub(crate) fn from_tantivy_results(
+        request: &BurkazTermsAggregation,
+        schema: &Schema,
+        prefix: &str,
+        results: &mut AggregationResults,
+    ) -> crate::Result<Self> {
+        let entry = field_entry(schema, request.field_id)?;
+        let AggregationResult::BucketResult(BucketResult::Terms {
+            buckets,
+            sum_other_doc_count,
+            ..
+        }) = take_result(results, &format!("{prefix}{TERMS}"))?
+        else {
+            return Err(unexpected_result(TERMS));
+        };
+
+        Ok(Self {
+            buckets: buckets
+                .into_iter()
+                .map(|bucket| BurkazTermsBucket {
+                    key: BurkazBucketKey::from_tantivy_key(bucket.key, &entry),
+                    doc_count: bucket.doc_count,
+                })
+                .collect(),
+            sum_other_doc_count,
+        })
+    }
+}
+
+/// Summary statistics of a numeric fast field.
+#[derive(Debug, Clone, Default)]
+pub struct BurkazMetricsAggregation {
+    pub field_id: u32,
+    /// Percentiles to estimate, between `0` and `100`. Empty skips them.
+    pub percents: Vec<f64>,
+}
+
+impl BurkazMetricsAggregation {
+    pub(crate) fn to_tantivy_aggregations(
+        &self,
+        schema: &Schema,
+        prefix: &str,
+        aggregations: &mut Aggregations,
+    ) -> crate::Result<()> {
+        let entry = field_entry(schema, self.field_id)?;
+        if !matches!(
+            entry.field_type(),
+            FieldType::I64(_) | FieldType::U64(_) | FieldType::F64(_)
+        ) {
+            return Err(BurkazError::InvalidField(format!(
+                "cannot compute metrics of field `{}`, only numeric fields are supported",
+                entry.name()
+            )));
+        }
+
+        if !entry.is_fast() {
+            return Err(BurkazError::InvalidField(format!(
+                "cannot compute metrics of field `{}`, it is not declared as fast",
+                entry.name()
+            )));
+        }
+
+        let field = entry.name().to_owned();
+        aggregations.insert(
+            format!("{prefix}{STATS}"),
+            aggregation(AggregationVariants::Stats(
+                StatsAggregation::from_field_name(field.clone()),
+            )),
+        );
+
+        if !self.percents.is_empty() {
+            aggregations.insert(
+                format!("{prefix}{PERCENTILES}"),
+                aggregation(AggregationVariants::Percentiles(
+                    PercentilesAggregationReq {
+                        percents: Some(self.percents.clone()),
+                        ..PercentilesAggregationReq::from_field_name(field)
+                    },
+                )),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BurkazPercentile {
+    pub percent: f64,
+    /// Estimated value, `None` when no object has a value.
+    pub value: Option<f64>,
+}
+
+/// Statistics of the values of the matching objects. `min`, `max` and `avg` are
+/// `None` when no object has a value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BurkazMetricsAggregationResult {
+    pub count: u64,
+    pub sum: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub avg: Option<f64>,
+    /// The requested percentiles, in the order they were requested.
+    pub percentiles: Vec<BurkazPercentile>,
+}
+
+impl BurkazMetricsAggregationResult {
+    pub(crate) fn from_tantivy_results(
+        request: &BurkazMetricsAggregation,
+        prefix: &str,
+        results: &mut AggregationResults,
+    ) -> crate::Result<Self> {
+        let AggregationResult::MetricResult(MetricResult::Stats(stats)) =
+            take_result(results, &format!("{prefix}{STATS}"))?
+        else {
+            return Err(unexpected_result(STATS));
+        };
+
+        let mut percentiles = Vec::with_capacity(request.percents.len());
+        if !request.percents.is_empty() {
+            let AggregationResult::MetricResult(MetricResult::Percentiles(result)) =
+                take_result(results, &format!("{prefix}{PERCENTILES}"))?
+            else {
+                return Err(unexpected_result(PERCENTILES));
+            };
+            let PercentileValues::HashMap(values) = result.values else {
+                return Err(unexpected_result(PERCENTILES));
+            };
+
+            // Keyed percentiles are formatted as strings, so they are parsed back to be
+            // matched with the requested ones.
+            let values = values
+                .into_iter()
+                .filter_map(|(percent, value)| Some((percent.parse::<f64>().ok()?, value)))
+                .collect::<Vec<_>>();
+            for percent in &request.percents {
+                let value = values
+                    .iter()
+                    .find(|(candidate, _)| candidate == percent)
+                    .map(|(_, value)| *value)
+                    .filter(|value| !value.is_nan());
+                percentiles.push(BurkazPercentile {
+                    percent: *percent,
+                    value,
+                });
+            }
+        }
+
+        Ok(Self {
+            count: stats.count,
+            sum: stats.sum,
+            min: stats.min,
+            max: stats.max,
+            avg: stats.avg,
+            percentiles,
+        })
+    }
+}
+
+/// Buckets the matching objects by fixed size ranges of a numeric or date fast
+/// field.
+///
+/// Values of date fields, that is the interval, the bounds and the bucket keys,
+/// are given in microseconds since the Unix epoch and must be whole milliseconds.
+#[derive(Debug, Clone, Default)]
+pub struct BurkazHistogramAggregation {
+    pub field_id: u32,
+    /// Width of the buckets, the first one starts at a multiple of it.
+    pub interval: f64,
+    /// Buckets with fewer objects are left out. Must be `0` with extended bounds.
+    pub min_doc_count: u64,
+    /// Range `(min, max)` for which buckets are returned even when empty.
+    pub extended_bounds: Option<(f64, f64)>,
+    /// Aggregations computed over the objects of every bucket.
+    pub sub_aggregations: Vec<BurkazAggregation>,
+}
+
+impl BurkazHistogramAggregation {
+    pub(crate) fn to_tantivy_aggregations(
+        &self,
+        schema: &Schema,
+        prefix: &str,
+        aggregations: &mut Aggregations,
+    ) -> crate::Result<()> {
+        let entry = field_entry(schema, self.field_id)?;
+        if !entry.is_fast() {
+            return Err(BurkazError::InvalidField(format!(
+                "cannot build a histogram of field `{}`, it is not declared as fast",
+                entry.name()
+            )));
+        }
+
+        let field = entry.name().to_owned();
+        let histogram = match entry.field_type() {
+            FieldType::I64(_) | FieldType::U64(_) | FieldType::F64(_) => {
+                AggregationVariants::Histogram(HistogramAggregation {
+                    field,
+                    interval: self.interval,
+                    min_doc_count: Some(self.min_doc_count),
+                    extended_bounds: self
+                        .extended_bounds
+                        .map(|(min, max)| HistogramBounds { min, max }),
+                    ..Default::default()
+                })
+            }
+            FieldType::Date(_) => {
+                let interval = self.interval / MICROS_PER_MILLI;
+                if interval < 1.0 || interval.fract() != 0.0 {
+                    return Err(BurkazError::InvalidAggregation(format!(
+                        "date histogram interval of field `{field}` must be a whole number of milliseconds"
+                    )));
+                }
+                AggregationVariants::DateHistogram(DateHistogramAggregationReq {
+                    field,
+                    fixed_interval: Some(format!("{interval}ms")),
+                    min_doc_count: Some(self.min_doc_count),
+                    extended_bounds: self.extended_bounds.map(|(min, max)| HistogramBounds {
+                        min: min / MICROS_PER_MILLI,
+                        max: max / MICROS_PER_MILLI,
+                    }),
+                    ..Default::default()
+                })
+            }
+            _ => {
+                return Err(BurkazError::InvalidField(format!(
+                    "cannot build a histogram of field `{}`, only numeric and date fields are supported",
+                    entry.name()
+                )));
+            }
+        };
+
+        let mut sub_aggregations = Aggregations::new();
+        for (index, sub_aggregation) in self.sub_aggregations.iter().enumerate() {
+            sub_aggregation.to_tantivy_aggregations(
+                schema,
+                &format!("{index}."),
+                &mut sub_aggregations,
+            )?;
+        }
+
+        aggregations.insert(
+            format!("{prefix}{HISTOGRAM}"),
+            Aggregation {
+                agg: histogram,
+                sub_aggregation: sub_aggregations,
+            },
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BurkazHistogramBucket {
+    /// Start of the bucket range.
+    pub key: f64,
+    pub doc_count: u64,
+    /// Results of the sub-aggregations, in the order they were requested.
+    pub sub_aggregations: Vec<BurkazAggregationResult>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BurkazHistogramAggregationResult {
+    /// The buckets, ordered by key.
+    pub buckets: Vec<BurkazHistogramBucket>,
+}
+
+impl BurkazHistogramAggregationResult {
+    pub(crate) fn from_tantivy_results(
+        request: &BurkazHistogramAggregation,
+        schema: &Schema,
+        prefix: &str,
+        results: &mut AggregationResults,
+    ) -> crate::Result<Self> {
+        let entry = field_entry(schema, request.field_id)?;
+        let AggregationResult::BucketResult(BucketResult::Histogram {
+            buckets: BucketEntries::Vec(buckets),
+        }) = take_result(results, &format!("{prefix}{HISTOGRAM}"))?
+        else {
+            return Err(unexpected_result(HISTOGRAM));
+        };
+
+        let is_date = matches!(entry.field_type(), FieldType::Date(_));
+        let buckets = buckets
+            .into_iter()
+            .map(|mut bucket| {
+                let key = match bucket.key {
+                    Key::F64(key) => key,
+                    Key::I64(key) => key as f64,
+                    Key::U64(key) => key as f64,
+                    Key::Str(_) => return Err(unexpected_result(HISTOGRAM)),
+                };
+                let sub_aggregations = request
+                    .sub_aggregations
+                    .iter()
+                    .enumerate()
+                    .map(|(index, sub_aggregation)| {
+                        sub_aggregation.read_tantivy_results(
+                            schema,
+                            &format!("{index}."),
+                            &mut bucket.sub_aggregation,
+                        )
+                    })
+                    .collect::<crate::Result<_>>()?;
+                Ok(BurkazHistogramBucket {
+                    key: if is_date { key * MICROS_PER_MILLI } else { key },
+                    doc_count: bucket.doc_count,
+                    sub_aggregations,
+                })
+            })
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Self { buckets })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::{DateTime, TantivyDocument, schema::Field};
+
+    use super::*;
+    use crate::{
+        index::{BurkazDirectory, BurkazIndex},
+        query::BurkazQuery,
+        query_runner::QueryRunner,
+        schema::{
+            BurkazSchema, BurkazSchemaField, BurkazSchemaFieldOptions, BurkazSchemaFieldType,
+        },
+        term::BurkazTerm,
+    };
+
+    fn field_options(typ: BurkazSchemaFieldType, fast: bool) -> BurkazSchemaFieldOptions {
+        BurkazSchemaFieldOptions {
+            stored: false,
+            fast,
+            indexing_tokenizer: Some("raw".to_owned()),
+            ..BurkazSchemaFieldOptions::for_test(typ)
+        }
+    }
+
+    fn index() -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        for (name, typ, fast) in [
+            ("brand", BurkazSchemaFieldType::Text, true),
+            ("rating", BurkazSchemaFieldType::Int64, true),
+            ("in_stock", BurkazSchemaFieldType::Boolean, true),
+            ("price", BurkazSchemaFieldType::Int64, false),
+        ] {
+            schema.add_field(BurkazSchemaField::new(
+                name.to_owned(),
+                field_options(typ, fast),
+            ));
+        }
+        let index =
+            BurkazIndex::new("products".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+
+        let objects = [
+            ("acme", 5, true),
+            ("acme", 4, true),
+            ("acme", 5, false),
+            ("globex", 3, true),
+            ("globex", 5, false),
+            ("initech", 1, true),
+        ]
+        .into_iter()
+        .map(|(brand, rating, in_stock)| {
+            let mut object = TantivyDocument::new();
+            object.add_text(Field::from_field_id(0), brand);
+            object.add_i64(Field::from_field_id(1), rating);
+            object.add_bool(Field::from_field_id(2), in_stock);
+            object.add_i64(Field::from_field_id(3), rating * 10);
+            object
+        })
+        .collect();
+        index.add_all(objects).unwrap();
+        index
+    }
+
+    fn buckets(result: BurkazTermsAggregationResult) -> Vec<(BurkazBucketKey, u64)> {
+        result
+            .buckets
+            .into_iter()
+            .map(|bucket| (bucket.key, bucket.doc_count))
+            .collect()
+    }
+
+    #[test]
+    fn terms_aggregation_buckets_by_value() {
+        let index = index();
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+
+        let brands = runner
+            .terms_aggregation(&BurkazTermsAggregation {
+                field_id: 0,
+                size: 2,
+                min_doc_count: 1,
+            })
+            .unwrap();
+        assert_eq!(brands.sum_other_doc_count, 1);
+        assert_eq!(
+            buckets(brands),
+            vec![
+                (BurkazBucketKey::Text("acme".to_owned()), 3),
+                (BurkazBucketKey::Text("globex".to_owned()), 2),
+            ]
+        );
+
+        let ratings = runner
+            .terms_aggregation(&BurkazTermsAggregation {
+                field_id: 1,
+                size: 10,
+                min_doc_count: 2,
+            })
+            .unwrap();
+        assert_eq!(buckets(ratings), vec![(BurkazBucketKey::Int64(5), 3)]);
+
+        let in_stock = BurkazQuery::Term {
+            term: BurkazTerm::new(0, &"acme".into()).unwrap(),
+            indexing_strategy: Default::default(),
+        };
+        let runner = QueryRunner::new(index.downgrade(), in_stock);
+        let stock = runner
+            .terms_aggregation(&BurkazTermsAggregation {
+                field_id: 2,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            buckets(stock),
+            vec![
+                (BurkazBucketKey::Boolean(true), 2),
+                (BurkazBucketKey::Boolean(false), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn terms_aggregation_requires_fast_field() {
+        let index = index();
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+
+        for field_id in [3, 4] {
+            assert!(matches!(
+                runner.terms_aggregation(&BurkazTermsAggregation {
+                    field_id,
+                    ..Default::default()
+                }),
+                Err(BurkazError::InvalidField(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn metrics_aggregation_summarizes_values() {
+        let index = index();
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+
+        let ratings = runner
+            .metrics_aggregation(&BurkazMetricsAggregation {
+                field_id: 1,
+                percents: vec![50.0, 100.0],
+            })
+            .unwrap();
+        assert_eq!(ratings.count, 6);
+        assert_eq!(ratings.sum, 23.0);
+        assert_eq!(ratings.min, Some(1.0));
+        assert_eq!(ratings.max, Some(5.0));
+        assert_eq!(ratings.avg, Some(23.0 / 6.0));
+
+        // Percentiles are estimated within a 1% relative error.
+        let percents = ratings
+            .percentiles
+            .iter()
+            .map(|percentile| percentile.percent)
+            .collect::<Vec<_>>();
+        assert_eq!(percents, vec![50.0, 100.0]);
+        let median = ratings.percentiles[0].value.unwrap();
+        assert!((median - 5.0).abs() < 0.1 || (median - 4.0).abs() < 0.1);
+        assert!((ratings.percentiles[1].value.unwrap() - 5.0).abs() < 0.1);
+
+        let nothing = QueryRunner::new(index.downgrade(), BurkazQuery::Empty)
+            .metrics_aggregation(&BurkazMetricsAggregation {
+                field_id: 1,
+                percents: vec![50.0],
+            })
+            .unwrap();
+        assert_eq!(nothing.count, 0);
+        assert_eq!(nothing.min, None);
+        assert_eq!(nothing.avg, None);
+        assert_eq!(nothing.percentiles[0].value, None);
+    }
+
+    #[test]
+    fn metrics_aggregation_requires_numeric_fast_field() {
+        let index = index();
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+
+        for field_id in [0, 2, 3] {
+            assert!(matches!(
+                runner.metrics_aggregation(&BurkazMetricsAggregation {
+                    field_id,
+                    ..Default::default()
+                }),
+                Err(BurkazError::InvalidField(_))
+            ));
+        }
+    }
+
+    const MICROS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+
+    fn sales_index() -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        for (name, typ) in [
+            ("amount", BurkazSchemaFieldType::F64),
+            ("sold_at", BurkazSchemaFieldType::Date),
+            ("brand", BurkazSchemaFieldType::Text),
+        ] {
+            schema.add_field(BurkazSchemaField::new(
+                name.to_owned(),
+                field_options(typ, true),
+            ));
+        }
+        let index =
+            BurkazIndex::new("sales".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+
+        let objects = [
+            (5.0, 0, "acme"),
+            (12.0, 0, "acme"),
+            (18.0, 1, "globex"),
+            (31.0, 3, "acme"),
+        ]
+        .into_iter()
+        .map(|(amount, day, brand)| {
+            let mut object = TantivyDocument::new();
+            object.add_f64(Field::from_field_id(0), amount);
+            object.add_date(
+                Field::from_field_id(1),
+                DateTime::from_timestamp_micros(day * MICROS_PER_DAY),
+            );
+            object.add_text(Field::from_field_id(2), brand);
+            object
+        })
+        .collect();
+        index.add_all(objects).unwrap();
+        index
+    }
+
+    fn histogram_buckets(result: &BurkazHistogramAggregationResult) -> Vec<(f64, u64)> {
+        result
+            .buckets
+            .iter()
+            .map(|bucket| (bucket.key, bucket.doc_count))
+            .collect()
+    }
+
+    #[test]
+    fn histogram_aggregation_buckets_by_range() {
+        let index = sales_index();
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+
+        let amounts = runner
+            .histogram_aggregation(&BurkazHistogramAggregation {
+                field_id: 0,
+                interval: 10.0,
+                min_doc_count: 1,
+                sub_aggregations: vec![
+                    BurkazAggregation::Metrics(BurkazMetricsAggregation {
+                        field_id: 0,
+                        percents: Vec::new(),
+                    }),
+                    BurkazAggregation::Terms(BurkazTermsAggregation {
+                        field_id: 2,
+                        ..Default::default()
+                    }),
+                ],
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            histogram_buckets(&amounts),
+            vec![(0.0, 1), (10.0, 2), (30.0, 1)]
+        );
+
+        let second = &amounts.buckets[1].sub_aggregations;
+        let BurkazAggregationResult::Metrics(metrics) = &second[0] else {
+            panic!("expected a metrics result");
+        };
+        assert_eq!(metrics.sum, 30.0);
+        let BurkazAggregationResult::Terms(terms) = &second[1] else {
+            panic!("expected a terms result");
+        };
+        let mut brands = buckets(terms.clone());
+        brands.sort_by_key(|(key, _)| format!("{key:?}"));
+        assert_eq!(
+            brands,
+            vec![
+                (BurkazBucketKey::Text("acme".to_owned()), 1),
+                (BurkazBucketKey::Text("globex".to_owned()), 1),
+            ]
+        );
+
+        let bounded = runner
+            .histogram_aggregation(&BurkazHistogramAggregation {
+                field_id: 0,
+                interval: 10.0,
+                min_doc_count: 0,
+                extended_bounds: Some((0.0, 50.0)),
+                sub_aggregations: Vec::new(),
+            })
+            .unwrap();
+        assert_eq!(
+            histogram_buckets(&bounded),
+            vec![
+                (0.0, 1),
+                (10.0, 2),
+                (20.0, 0),
+                (30.0, 1),
+                (40.0, 0),
+                (50.0, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn date_histogram_aggregation_uses_microseconds() {
+        let index = sales_index();
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+
+        let days = runner
+            .histogram_aggregation(&BurkazHistogramAggregation {
+                field_id: 1,
+                interval: MICROS_PER_DAY as f64,
+                min_doc_count: 0,
+                ..Default::default()
+            })
+            .unwrap();
+        let day = MICROS_PER_DAY as f64;
+        assert_eq!(
+            histogram_buckets(&days),
+            vec![(0.0, 2), (day, 1), (2.0 * day, 0), (3.0 * day, 1)]
+        );
+
+        assert!(matches!(
+            runner.histogram_aggregation(&BurkazHistogramAggregation {
+                field_id: 1,
+                interval: 1.5,
+                ..Default::default()
+            }),
+            Err(BurkazError::InvalidAggregation(_))
+        ));
+        assert!(matches!(
+            runner.histogram_aggregation(&BurkazHistogramAggregation {
+                field_id: 2,
+                interval: 10.0,
+                ..Default::default()
+            }),
+            Err(BurkazError::InvalidField(_))
+        ));
+    }
+}
@@ -0,0 +1,611 @@
+use tantivy::tokenizer::{
+    AlphaNumOnlyFilter, AsciiFoldingFilter, Language, LowerCaser, NgramTokenizer, RawTokenizer,
+    RegexTokenizer, RemoveLongFilter, SimpleTokenizer, SplitCompoundWords, Stemmer, StopWordFilter,
+    TextAnalyzer, Token, TokenFilter, TokenStream, Tokenizer, TokenizerManager,
+    WhitespaceTokenizer,
+};
+
+use crate::error::BurkazError;
+
+/// Tokens of this many bytes or more are dropped by the built-in analyzers.
+const TOKEN_LENGTH_LIMIT: usize = 40;
+
+/// Name of the built-in analyzer indexing the prefixes of every word, used by
+/// search-as-you-type fields.
+pub const AUTOCOMPLETE_ANALYZER_NAME: &str = "autocomplete";
+
+/// Longest prefix indexed by the autocomplete analyzer, in characters.
+pub const AUTOCOMPLETE_MAX_GRAM: usize = 20;
+
+/// Tantivy has no Turkish stop words, these are the most frequent ones.
+const TURKISH_STOP_WORDS: &[&str] = &[
+    "acaba", "ama", "ancak", "bana", "bazı", "belki", "ben", "beni", "benim", "bir", "biraz",
+    "birçok", "biri", "birkaç", "bu", "bunu", "bunun", "buna", "çok", "çünkü", "da", "daha", "de",
+    "defa", "diye", "en", "gibi", "hem", "hep", "hepsi", "her", "hiç", "için", "ile", "ise", "kez",
+    "ki", "kim", "mı", "mi", "mu", "mü", "nasıl", "ne", "neden", "nerede", "niçin", "o", "ona",
+    "onu", "onun", "sen", "siz", "şey", "şu", "tüm", "ve", "veya", "ya", "yani",
+];
+
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BurkazLanguage {
+    Arabic = 1,
+    Danish = 2,
+    Dutch = 3,
+    English = 4,
+    Finnish = 5,
+    French = 6,
+    German = 7,
+    Greek = 8,
+    Hungarian = 9,
+    Italian = 10,
+    Norwegian = 11,
+    Portuguese = 12,
+    Romanian = 13,
+    Russian = 14,
+    Spanish = 15,
+    Swedish = 16,
+    Tamil = 17,
+    Turkish = 18,
+}
+
+impl BurkazLanguage {
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(BurkazLanguage::Arabic),
+            2 => Some(BurkazLanguage::Danish),
+            3 => Some(BurkazLanguage::Dutch),
+            4 => Some(BurkazLanguage::English),
+            5 => Some(BurkazLanguage::Finnish),
+            6 => Some(BurkazLanguage::French),
+            7 => Some(BurkazLanguage::German),
+            8 => Some(BurkazLanguage::Greek),
+            9 => Some(BurkazLanguage::Hungarian),
+            10 => Some(BurkazLanguage::Italian),
+            11 => Some(BurkazLanguage::Norwegian),
+            12 => Some(BurkazLanguage::Portuguese),
+            13 => Some(BurkazLanguage::Romanian),
+            14 => Some(BurkazLanguage::Russian),
+            15 => Some(BurkazLanguage::Spanish),
+            16 => Some(BurkazLanguage::Swedish),
+            17 => Some(BurkazLanguage::Tamil),
+            18 => Some(BurkazLanguage::Turkish),
+            _ => None,
+        }
+    }
+}
+
+impl From<BurkazLanguage> for Language {
+    fn from(language: BurkazLanguage) -> Self {
+        match language {
+            BurkazLanguage::Arabic => Language::Arabic,
+            BurkazLanguage::Danish => Language::Danish,
+            BurkazLanguage::Dutch => Language::Dutch,
+            BurkazLanguage::English => Language::English,
+            BurkazLanguage::Finnish => Language::Finnish,
+            BurkazLanguage::French => Language::French,
+            BurkazLanguage::German => Language::German,
+            BurkazLanguage::Greek => Language::Greek,
+            BurkazLanguage::Hungarian => Language::Hungarian,
+            BurkazLanguage::Italian => Language::Italian,
+            BurkazLanguage::Norwegian => Language::Norwegian,
+            BurkazLanguage::Portuguese => Language::Portuguese,
+            BurkazLanguage::Romanian => Language::Romanian,
+            BurkazLanguage::Russian => Language::Russian,
+            BurkazLanguage::Spanish => Language::Spanish,
+            BurkazLanguage::Swedish => Language::Swedish,
+            BurkazLanguage::Tamil => Language::Tamil,
+            BurkazLanguage::Turkish => Language::Turkish,
+        }
+    }
+}
+
+/// Splits the text into tokens before the filters are applied.
+#[derive(Debug, Clone)]
+pub enum BurkazTokenizer {
+    /// Splits on every character that is not alphanumeric.
+    Simple,
+    Whitespace,
+    /// Keeps the whole text as a single token.
+    Raw,
+    /// Emits every n-gram of the text, or only its prefixes.
+    Ngram {
+        min_gram: usize,
+        max_gram: usize,
+        prefix_only: bool,
+    },
+    /// Emits every match of the pattern.
+    Regex(String),
+}
+
+/// Transforms the tokens, filters are applied in the order they are given.
+#[derive(Debug, Clone)]
+pub enum BurkazTokenFilter {
+    LowerCase,
+    /// Replaces non-ASCII characters by their ASCII equivalent, if any.
+    AsciiFolding,
+    /// Removes tokens holding characters that are not ASCII alphanumerics.
+    AlphaNumOnly,
+    /// Removes the built-in stop words of the language.
+    StopWords(BurkazLanguage),
+    /// Removes the given words.
+    StopWordList(Vec<String>),
+    Stemmer(BurkazLanguage),
+    /// Removes tokens of at least the given number of bytes.
+    RemoveLong(usize),
+    /// Splits compound words made only of the given words, other tokens are
+    /// kept as they are.
+    SplitCompound(Vec<String>),
+    /// Replaces every token by its prefixes of `min_gram` to `max_gram`
+    /// characters, all at the position of the token. Shorter tokens are
+    /// removed.
+    EdgeNgram {
+        min_gram: usize,
+        max_gram: usize,
+    },
+    /// Lowercases with the Turkish casing rules, where `I` becomes `ı` and `İ`
+    /// becomes `i`.
+    TurkishLowerCase,
+}
+
+/// A named text analyzer that fields reference through their tokenizer names.
+#[derive(Debug, Clone)]
+pub struct BurkazAnalyzer {
+    pub name: String,
+    pub tokenizer: BurkazTokenizer,
+    pub filters: Vec<BurkazTokenFilter>,
+}
+
+impl BurkazAnalyzer {
+    pub fn new(name: String, tokenizer: BurkazTokenizer, filters: Vec<BurkazTokenFilter>) -> Self {
+        Self {
+            name,
+            tokenizer,
+            filters,
+        }
+    }
+
+    pub fn build(&self) -> crate::Result<TextAnalyzer> {
+        let mut builder = match &self.tokenizer {
+            BurkazTokenizer::Simple => TextAnalyzer::builder(SimpleTokenizer::default()).dynamic(),
+            BurkazTokenizer::Whitespace => {
+                TextAnalyzer::builder(WhitespaceTokenizer::default()).dynamic()
+            }
+            BurkazTokenizer::Raw => TextAnalyzer::builder(RawTokenizer::default()).dynamic(),
+            BurkazTokenizer::Ngram {
+                min_gram,
+                max_gram,
+                prefix_only,
+            } => TextAnalyzer::builder(NgramTokenizer::new(*min_gram, *max_gram, *prefix_only)?)
+                .dynamic(),
+            BurkazTokenizer::Regex(pattern) => {
+                TextAnalyzer::builder(RegexTokenizer::new(pattern)?).dynamic()
+            }
+        };
+
+        for filter in &self.filters {
+            builder = match filter {
+                BurkazTokenFilter::LowerCase => builder.filter_dynamic(LowerCaser),
+                BurkazTokenFilter::AsciiFolding => builder.filter_dynamic(AsciiFoldingFilter),
+                BurkazTokenFilter::AlphaNumOnly => builder.filter_dynamic(AlphaNumOnlyFilter),
+                BurkazTokenFilter::StopWords(language) => {
+                    let filter = StopWordFilter::new((*language).into()).ok_or_else(|| {
+                        BurkazError::InvalidSchema(format!(
+                            "analyzer `{}`: no stop words for {:?}",
+                            self.name, language
+                        ))
+                    })?;
+                    builder.filter_dynamic(filter)
+                }
+                BurkazTokenFilter::StopWordList(words) => {
+                    builder.filter_dynamic(StopWordFilter::remove(words.iter().cloned()))
+                }
+                BurkazTokenFilter::Stemmer(language) => {
+                    builder.filter_dynamic(Stemmer::new((*language).into()))
+                }
+                BurkazTokenFilter::RemoveLong(length_limit) => {
+                    builder.filter_dynamic(RemoveLongFilter::limit(*length_limit))
+                }
+                BurkazTokenFilter::SplitCompound(words) => {
+                    builder.filter_dynamic(SplitCompoundWords::from_dictionary(words)?)
+                }
+                BurkazTokenFilter::EdgeNgram { min_gram, max_gram } => {
+                    if *min_gram == 0 || min_gram > max_gram {
+                        return Err(BurkazError::InvalidSchema(format!(
+                            "analyzer `{}`: edge n-grams need 0 < min_gram <= max_gram",
+                            self.name
+                        )));
+                    }
+                    builder.filter_dynamic(EdgeNgramFilter {
+                        min_gram: *min_gram,
+                        max_gram: *max_gram,
+                    })
+                }
+                BurkazTokenFilter::TurkishLowerCase => builder.filter_dynamic(TurkishLowerCaser),
+            };
+        }
+
+        Ok(builder.build())
+    }
+
+    /// Analyzers registered on every index, named after their language:
+    /// `english`, `russian`, `german` and `turkish`.
+    ///
+    /// They lowercase the tokens, with the Turkish casing rules for `turkish`,
+    /// remove the stop words, stem and finally fold them to ASCII, so queries
+    /// match regardless of diacritics.
+    ///
+    /// The [`AUTOCOMPLETE_ANALYZER_NAME`] analyzer is registered as well.
+    pub fn built_in() -> Vec<Self> {
+        let autocomplete = Self::new(
+            AUTOCOMPLETE_ANALYZER_NAME.to_owned(),
+            BurkazTokenizer::Simple,
+            autocomplete_filters(vec![BurkazTokenFilter::EdgeNgram {
+                min_gram: 1,
+                max_gram: AUTOCOMPLETE_MAX_GRAM,
+            }]),
+        );
+
+        [
+            ("english", BurkazLanguage::English),
+            ("russian", BurkazLanguage::Russian),
+            ("german", BurkazLanguage::German),
+            ("turkish", BurkazLanguage::Turkish),
+        ]
+        .into_iter()
+        .map(|(name, language)| {
+            let lower_case = match language {
+                BurkazLanguage::Turkish => BurkazTokenFilter::TurkishLowerCase,
+                _ => BurkazTokenFilter::LowerCase,
+            };
+            let stop_words = match language {
+                BurkazLanguage::Turkish => BurkazTokenFilter::StopWordList(
+                    TURKISH_STOP_WORDS
+                        .iter()
+                        .map(|&word| word.to_owned())
+                        .collect(),
+                ),
+                language => BurkazTokenFilter::StopWords(language),
+            };
+            Self::new(
+                name.to_owned(),
+                BurkazTokenizer::Simple,
+                vec![
+                    BurkazTokenFilter::RemoveLong(TOKEN_LENGTH_LIMIT),
+                    lower_case,
+                    stop_words,
+                    BurkazTokenFilter::Stemmer(language),
+                    BurkazTokenFilter::AsciiFolding,
+                ],
+            )
+        })
+        .chain([autocomplete])
+        .collect()
+    }
+
+    /// Splits the text typed in a search box into the words to look up in
+    /// autocomplete fields, normalized the same way as the indexed prefixes.
+    pub fn autocomplete_words(text: &str) -> Vec<String> {
+        let search = Self::new(
+            AUTOCOMPLETE_ANALYZER_NAME.to_owned(),
+            BurkazTokenizer::Simple,
+            autocomplete_filters(vec![]),
+        );
+        let mut analyzer = search.build().expect("autocomplete analyzer is valid");
+        let mut stream = analyzer.token_stream(text);
+        let mut words = Vec::new();
+        while let Some(token) = stream.next() {
+            let word = match token.text.char_indices().nth(AUTOCOMPLETE_MAX_GRAM) {
+                Some((end, _)) => &token.text[..end],
+                None => &token.text,
+            };
+            if !words.iter().any(|known| known == word) {
+                words.push(word.to_owned());
+            }
+        }
+        words
+    }
+
+    /// Registers the analyzer under its name, replacing any tokenizer with the
+    /// same name.
+    pub fn register(&self, tokenizers: &TokenizerManager) -> crate::Result<()> {
+        tokenizers.register(&self.name, self.build()?);
+        Ok(())
+    }
+}
+
+fn autocomplete_filters(mut filters: Vec<BurkazTokenFilter>) -> Vec<BurkazTokenFilter> {
+    let mut normalization = vec![
+        BurkazTokenFilter::RemoveLong(TOKEN_LENGTH_LIMIT),
+        BurkazTokenFilter::LowerCase,
+        BurkazTokenFilter::AsciiFolding,
+    ];
+    normalization.append(&mut filters);
+    normalization
+}
+
+#[derive(Clone)]
+struct EdgeNgramFilter {
+    min_gram: usize,
+    max_gram: usize,
+}
+
+impl TokenFilter for EdgeNgramFilter {
+    type Tokenizer<T: Tokenizer> = EdgeNgramFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> EdgeNgramFilterWrapper<T> {
+        EdgeNgramFilterWrapper {
+            filter: self,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct EdgeNgramFilterWrapper<T> {
+    filter: EdgeNgramFilter,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for EdgeNgramFilterWrapper<T> {
+    type TokenStream<'a> = EdgeNgramTokenStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        EdgeNgramTokenStream {
+            filter: self.filter.clone(),
+            tail: self.inner.token_stream(text),
+            word: String::new(),
+            ends: Vec::new(),
+            token: Token::default(),
+        }
+    }
+}
+
+struct EdgeNgramTokenStream<T> {
+    filter: EdgeNgramFilter,
+    tail: T,
+    /// Token being split into prefixes.
+    word: String,
+    /// Byte ends of the prefixes left to emit, the next one last.
+    ends: Vec<usize>,
+    token: Token,
+}
+
+impl<T: TokenStream> TokenStream for EdgeNgramTokenStream<T> {
+    fn advance(&mut self) -> bool {
+        loop {
+            if let Some(end) = self.ends.pop() {
+                self.token.text.clear();
+                self.token.text.push_str(&self.word[..end]);
+                return true;
+            }
+
+            if !self.tail.advance() {
+                return false;
+            }
+            self.token.clone_from(self.tail.token());
+            self.word.clone_from(&self.token.text);
+            self.ends = self
+                .word
+                .char_indices()
+                .map(|(start, char)| start + char.len_utf8())
+                .skip(self.filter.min_gram - 1)
+                .take(self.filter.max_gram - self.filter.min_gram + 1)
+                .collect();
+            self.ends.reverse();
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+#[derive(Clone)]
+struct TurkishLowerCaser;
+
+impl TokenFilter for TurkishLowerCaser {
+    type Tokenizer<T: Tokenizer> = TurkishLowerCaserWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> TurkishLowerCaserWrapper<T> {
+        TurkishLowerCaserWrapper(tokenizer)
+    }
+}
+
+#[derive(Clone)]
+struct TurkishLowerCaserWrapper<T>(T);
+
+impl<T: Tokenizer> Tokenizer for TurkishLowerCaserWrapper<T> {
+    type TokenStream<'a> = TurkishLowerCaserTokenStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        TurkishLowerCaserTokenStream {
+            tail: self.0.token_stream(text),
+            buffer: String::new(),
+        }
+    }
+}
+
+struct TurkishLowerCaserTokenStream<T> {
+    tail: T,
+    buffer: String,
+}
+
+impl<T: TokenStream> TokenStream for TurkishLowerCaserTokenStream<T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        self.buffer.clear();
+        for char in self.tail.token().text.chars() {
+            match char {
+                'I' => self.buffer.push('ı'),
+                'İ' => self.buffer.push('i'),
+                char => self.buffer.extend(char.to_lowercase()),
+            }
+        }
+        std::mem::swap(&mut self.tail.token_mut().text, &mut self.buffer);
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(analyzer: &BurkazAnalyzer, text: &str) -> Vec<String> {
+        let mut analyzer = analyzer.build().unwrap();
+        let mut stream = analyzer.token_stream(text);
+        let mut tokens = Vec::new();
+        while let Some(token) = stream.next() {
+            tokens.push(token.text.clone());
+        }
+        tokens
+    }
+
+    #[test]
+    fn filters_apply_in_order() {
+        let analyzer = BurkazAnalyzer::new(
+            "custom".to_owned(),
+            BurkazTokenizer::Simple,
+            vec![
+                BurkazTokenFilter::RemoveLong(12),
+                BurkazTokenFilter::LowerCase,
+                BurkazTokenFilter::AsciiFolding,
+                BurkazTokenFilter::StopWordList(vec!["the".to_owned()]),
+                BurkazTokenFilter::SplitCompound(vec!["foot".to_owned(), "ball".to_owned()]),
+                BurkazTokenFilter::Stemmer(BurkazLanguage::English),
+            ],
+        );
+        assert_eq!(
+            tokens(
+                &analyzer,
+                "The Café hosts Football matches, extraordinarily"
+            ),
+            vec!["cafe", "host", "foot", "ball", "match"]
+        );
+
+        let ngrams = BurkazAnalyzer::new(
+            "ngram3".to_owned(),
+            BurkazTokenizer::Ngram {
+                min_gram: 3,
+                max_gram: 3,
+                prefix_only: false,
+            },
+            vec![],
+        );
+        assert_eq!(tokens(&ngrams, "burkaz"), vec!["bur", "urk", "rka", "kaz"]);
+    }
+
+    #[test]
+    fn invalid_analyzers_fail_to_build() {
+        let no_stop_words = BurkazAnalyzer::new(
+            "tamil".to_owned(),
+            BurkazTokenizer::Simple,
+            vec![BurkazTokenFilter::StopWords(BurkazLanguage::Tamil)],
+        );
+        assert!(matches!(
+            no_stop_words.build(),
+            Err(BurkazError::InvalidSchema(_))
+        ));
+
+        let bad_ngrams = BurkazAnalyzer::new(
+            "ngram".to_owned(),
+            BurkazTokenizer::Ngram {
+                min_gram: 4,
+                max_gram: 2,
+                prefix_only: true,
+            },
+            vec![],
+        );
+        assert!(bad_ngrams.build().is_err());
+    }
+
+    fn built_in(name: &str) -> BurkazAnalyzer {
+        BurkazAnalyzer::built_in()
+            .into_iter()
+            .find(|analyzer| analyzer.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn english_analyzer() {
+        let analyzer = built_in("english");
+        assert_eq!(
+            tokens(&analyzer, "The Runners were running to the Cafés"),
+            tokens(&analyzer, "runner were run cafe")
+        );
+        assert_eq!(tokens(&analyzer, "the of and"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn russian_analyzer() {
+        let analyzer = built_in("russian");
+        assert_eq!(
+            tokens(&analyzer, "Книги и журналы"),
+            tokens(&analyzer, "книгами журналов")
+        );
+        assert_eq!(tokens(&analyzer, "и в на"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn german_analyzer() {
+        let analyzer = built_in("german");
+        assert_eq!(
+            tokens(&analyzer, "Die Häuser und der Bäcker"),
+            tokens(&analyzer, "Haus Bäckers")
+        );
+        assert_eq!(tokens(&analyzer, "die und der"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn turkish_analyzer() {
+        let analyzer = built_in("turkish");
+        assert_eq!(
+            tokens(&analyzer, "kitaplar ve defterler için"),
+            tokens(&analyzer, "kitap defter")
+        );
+        assert_eq!(tokens(&analyzer, "çiçekler"), vec!["cicek"]);
+        assert_eq!(
+            tokens(&analyzer, "KIRMIZI ÇİÇEKLER"),
+            tokens(&analyzer, "kırmızı çiçekler")
+        );
+        assert_eq!(tokens(&analyzer, "İÇİN VE"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn edge_ngrams_index_word_prefixes() {
+        let analyzer = built_in(AUTOCOMPLETE_ANALYZER_NAME);
+        assert_eq!(
+            tokens(&analyzer, "iPhone Ü"),
+            vec!["i", "ip", "iph", "ipho", "iphon", "iphone", "u"]
+        );
+
+        let bounded = BurkazAnalyzer::new(
+            "edge".to_owned(),
+            BurkazTokenizer::Simple,
+            vec![BurkazTokenFilter::EdgeNgram {
+                min_gram: 2,
+                max_gram: 3,
+            }],
+        );
+        assert_eq!(tokens(&bounded, "a çay"), vec!["ça", "çay"]);
+
+        assert_eq!(
+            BurkazAnalyzer::autocomplete_words("IPH iph Über"),
+            vec!["iph", "uber"]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use std::cmp::Reverse;
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use tantivy::Score;
+
+use crate::{address::BurkazObjectAddr, error::BurkazError, query_runner::BurkazSortOrder};
+
+const CURSOR_VERSION: u8 = 1;
+const SORTED_FLAG: u8 = 1 << 0;
+const SORT_KEY_FLAG: u8 = 1 << 1;
+
+/// Total order of the objects in a page: the sort key, then the score, then the
+/// address so objects sharing a key and a score are never skipped nor repeated.
+/// Greater positions come first.
+pub(crate) type SearchPosition = (Option<u64>, Score, Reverse<u64>);
+
+/// Position of the last object of a page. Searching after it returns the next
+/// page without collecting the objects of the previous ones.
+///
+/// Addresses change when segments are merged, so cursors are only reliable
+/// when every page is read from the same [`crate::snapshot::BurkazSnapshot`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BurkazSearchCursor {
+    /// Field and order of sorted searches, `None` for searches by relevance.
+    pub sort: Option<(u32, BurkazSortOrder)>,
+    /// Sort key of the object, already mapped for the sort order.
+    pub sort_key: Option<u64>,
+    pub score: Score,
+    pub addr: BurkazObjectAddr,
+}
+
+impl BurkazSearchCursor {
+    pub(crate) fn position(&self) -> SearchPosition {
+        (self.sort_key, self.score, Reverse(self.addr.val()))
+    }
+
+    pub fn to_bytes(self) -> Vec<u8> {
+        let mut flags = 0;
+        if self.sort.is_some() {
+            flags |= SORTED_FLAG;
+        }
+        if self.sort_key.is_some() {
+            flags |= SORT_KEY_FLAG;
+        }
+        let (field_id, order) = self.sort.unwrap_or_default();
+
+        let mut bytes = Vec::with_capacity(27);
+        bytes.push(CURSOR_VERSION);
+        bytes.push(flags);
+        bytes.write_u32::<LittleEndian>(field_id).unwrap();
+        bytes.push(order as u8);
+        bytes
+            .write_u64::<LittleEndian>(self.sort_key.unwrap_or_default())
+            .unwrap();
+        bytes.write_f32::<LittleEndian>(self.score).unwrap();
+        bytes.write_u64::<LittleEndian>(self.addr.val()).unwrap();
+        bytes
+    }
+
+    pub fn from_bytes(mut bytes: &[u8]) -> crate::Result<Self> {
+        let invalid = || BurkazError::InvalidCursor("malformed cursor".to_owned());
+
+        let version = bytes.read_u8().map_err(|_| invalid())?;
+        if version != CURSOR_VERSION {
+            return Err(BurkazError::InvalidCursor(format!(
+                "unsupported cursor version {version}"
+            )));
+        }
+        let flags = bytes.read_u8().map_err(|_| invalid())?;
+        let field_id = bytes.read_u32::<LittleEndian>().map_err(|_| invalid())?;
+        let order = bytes.read_u8().map_err(|_| invalid())?;
+        let sort_key = bytes.read_u64::<LittleEndian>().map_err(|_| invalid())?;
+        let score = bytes.read_f32::<LittleEndian>().map_err(|_| invalid())?;
+        let addr = bytes.read_u64::<LittleEndian>().map_err(|_| invalid())?;
+        if !bytes.is_empty() {
+            return Err(invalid());
+        }
+
+        let sort = if flags & SORTED_FLAG != 0 {
+            Some((
+                field_id,
+                BurkazSortOrder::from_code(order).ok_or_else(invalid)?,
+            ))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            sort,
+            sort_key: (flags & SORT_KEY_FLAG != 0).then_some(sort_key),
+            score,
+            addr: addr.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_round_trips_through_bytes() {
+        for cursor in [
+            BurkazSearchCursor {
+                sort: None,
+                sort_key: None,
+                score: 1.5,
+                addr: 42.into(),
+            },
+            BurkazSearchCursor {
+                sort: Some((3, BurkazSortOrder::Ascending)),
+                sort_key: Some(7),
+                score: 0.0,
+                addr: (1u64 << 32 | 9).into(),
+            },
+        ] {
+            assert_eq!(
+                BurkazSearchCursor::from_bytes(&cursor.to_bytes()).unwrap(),
+                cursor
+            );
+        }
+
+        assert!(matches!(
+            BurkazSearchCursor::from_bytes(&[CURSOR_VERSION, 0]),
+            Err(BurkazError::InvalidCursor(_))
+        ));
+    }
+}
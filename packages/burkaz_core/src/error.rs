@@ -78,7 +78,19 @@ pub enum BurkazError {
     UnknownError(String),
     NullPointer(&'static str),
     ObjectNotFound(BurkazObjectAddr),
+    ObjectIdNotFound(u64),
     IndexClosed,
+    InvalidSchema(String),
+    MissingKey,
+    InvalidField(String),
+    InvalidFacet(String),
+    InvalidAggregation(String),
+    InvalidCursor(String),
+    InvalidQuery(String),
+    QueryParse {
+        position: Option<usize>,
+        message: String,
+    },
 }
 
 impl From<BurkazObjectAddr> for BurkazError {
@@ -100,7 +112,23 @@ impl std::fmt::Display for BurkazError {
             BurkazError::UnknownError(error) => write!(f, "Unknown error: {}", error),
             BurkazError::NullPointer(error) => write!(f, "Null pointer error: {}", error),
             BurkazError::ObjectNotFound(addr) => write!(f, "Object not found: {}", addr),
+            BurkazError::ObjectIdNotFound(id) => write!(f, "Object not found: id {}", id),
             BurkazError::IndexClosed => write!(f, "Index closed"),
+            BurkazError::InvalidSchema(error) => write!(f, "Invalid schema: {}", error),
+            BurkazError::MissingKey => write!(f, "Object has no value for the key field"),
+            BurkazError::InvalidField(error) => write!(f, "Invalid field: {}", error),
+            BurkazError::InvalidFacet(error) => write!(f, "Invalid facet: {}", error),
+            BurkazError::InvalidAggregation(error) => write!(f, "Invalid aggregation: {}", error),
+            BurkazError::InvalidCursor(error) => write!(f, "Invalid cursor: {}", error),
+            BurkazError::InvalidQuery(error) => write!(f, "Invalid query: {}", error),
+            BurkazError::QueryParse {
+                position: Some(position),
+                message,
+            } => write!(f, "Query parse error at position {}: {}", position, message),
+            BurkazError::QueryParse {
+                position: None,
+                message,
+            } => write!(f, "Query parse error: {}", message),
         }
     }
 }
@@ -114,7 +142,30 @@ impl std::fmt::Debug for BurkazError {
             BurkazError::ObjectNotFound(addr) => {
                 write!(f, "BurkazError::ObjectNotFound({:?})", addr)
             }
+            BurkazError::ObjectIdNotFound(id) => {
+                write!(f, "BurkazError::ObjectIdNotFound({:?})", id)
+            }
             BurkazError::IndexClosed => write!(f, "BurkazError::IndexClosed"),
+            BurkazError::InvalidSchema(error) => {
+                write!(f, "BurkazError::InvalidSchema({:?})", error)
+            }
+            BurkazError::MissingKey => write!(f, "BurkazError::MissingKey"),
+            BurkazError::InvalidField(error) => write!(f, "BurkazError::InvalidField({:?})", error),
+            BurkazError::InvalidFacet(error) => write!(f, "BurkazError::InvalidFacet({:?})", error),
+            BurkazError::InvalidAggregation(error) => {
+                write!(f, "BurkazError::InvalidAggregation({:?})", error)
+            }
+            BurkazError::InvalidCursor(error) => {
+                write!(f, "BurkazError::InvalidCursor({:?})", error)
+            }
+            BurkazError::InvalidQuery(error) => {
+                write!(f, "BurkazError::InvalidQuery({:?})", error)
+            }
+            BurkazError::QueryParse { position, message } => write!(
+                f,
+                "BurkazError::QueryParse {{ position: {:?}, message: {:?} }}",
+                position, message
+            ),
         }
     }
 }
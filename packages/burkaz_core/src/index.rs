@@ -1,19 +1,34 @@
 use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
     path::Path,
-    sync::{Arc, Mutex, MutexGuard, Weak},
+    sync::{
+        Arc, Mutex, MutexGuard, Weak,
+        atomic::{AtomicU64, Ordering},
+    },
 };
 
 use tantivy::{
-    Index, IndexBuilder, IndexReader, IndexWriter, Searcher, SegmentOrdinal, TantivyDocument,
-    TantivyError,
+    DocAddress, Index, IndexBuilder, IndexReader, IndexWriter, Searcher, SegmentOrdinal,
+    TantivyDocument, TantivyError,
+    collector::TopDocs,
     directory::{Directory, MmapDirectory},
     indexer::IndexWriterOptions,
-    query::{Query, QueryParser},
-    schema::FieldType,
+    query::{Query, QueryParser, TermQuery},
+    schema::{Field, FieldType, IndexRecordOption, Schema, Term, Value},
+    termdict::TermMerger,
 };
 
 use crate::error::BurkazError;
-use crate::{address::BurkazObjectAddr, schema::BurkazSchema};
+use crate::{
+    address::BurkazObjectAddr,
+    analyzer::{AUTOCOMPLETE_ANALYZER_NAME, BurkazAnalyzer},
+    query_runner::field_entry,
+    schema::{BurkazSchema, ID_FIELD_NAME},
+    snapshot::BurkazSnapshot,
+    term::BurkazTerm,
+    transaction::{BurkazTransaction, BurkazWriteOperation},
+};
 
 #[derive(Clone)]
 pub struct BurkazIndex(Arc<InnerBurkazIndex>);
@@ -32,6 +47,20 @@ struct InnerBurkazIndex {
     reader: IndexReader,
     writer: Arc<Mutex<IndexWriter<TantivyDocument>>>,
     query_parser: QueryParser,
+    key_field: Option<Field>,
+    id_field: Field,
+    /// Id given to the next added object, ids are never reused. It is saved
+    /// as the payload of every commit so it survives reopening the index.
+    next_id: AtomicU64,
+}
+
+/// A completion of a prefix found in the term dictionary of a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BurkazSuggestion {
+    pub term: String,
+    /// Number of objects holding the term, deleted objects are counted until
+    /// their segment is merged.
+    pub doc_freq: u64,
 }
 
 pub enum BurkazDirectory<'a> {
@@ -57,12 +86,24 @@ impl<'a> BurkazDirectory<'a> {
 }
 
 impl BurkazIndex {
+    /// Creates the index, or opens it when the directory already holds one
+    /// with the same schema.
+    ///
+    /// Indexes written before objects had stable ids lack the internal id
+    /// field and cannot be opened, they have to be deleted and filled again.
     pub fn new(
         name: String,
         schema: BurkazSchema,
         directory: BurkazDirectory,
     ) -> crate::Result<Self> {
-        let index_builder = IndexBuilder::new().schema(schema.into());
+        schema.validate_field_names()?;
+        let key_field = schema.key_field()?.map(Field::from_field_id);
+        let query_parser_options = schema.query_parser_options().clone();
+        let analyzers = schema.analyzers().to_vec();
+
+        let schema: Schema = schema.into();
+        let id_field = schema.get_field(ID_FIELD_NAME)?;
+        let index_builder = IndexBuilder::new().schema(schema);
 
         let index = match directory {
             BurkazDirectory::InMemory => index_builder
@@ -74,12 +115,31 @@ impl BurkazIndex {
                         .map_err(Into::<TantivyError>::into)
                         .map_err(Into::<BurkazError>::into)?,
                 ) as Box<dyn Directory>;
+                if Index::exists(directory.as_ref()).map_err(Into::<TantivyError>::into)?
+                    && Index::open(directory.box_clone())?
+                        .schema()
+                        .get_field(ID_FIELD_NAME)
+                        .is_err()
+                {
+                    return Err(BurkazError::InvalidSchema(format!(
+                        "index `{}` was created before objects had stable ids, delete it and add \
+                         the objects again",
+                        name
+                    )));
+                }
                 index_builder
                     .open_or_create(directory)
                     .map_err(Into::<BurkazError>::into)?
             }
         };
 
+        // Analyzers of the schema come last so they can replace built-in ones.
+        for analyzer in BurkazAnalyzer::built_in().iter().chain(&analyzers) {
+            analyzer.register(index.tokenizers())?;
+            analyzer.register(index.fast_field_tokenizer())?;
+        }
+        validate_tokenizers(&index)?;
+
         let writer_options = IndexWriterOptions::builder().build();
         let writer = index
             .writer_with_options::<TantivyDocument>(writer_options)
@@ -87,22 +147,23 @@ impl BurkazIndex {
 
         let reader = index.reader().map_err(Into::<BurkazError>::into)?;
 
-        let query_parser = {
-            let schema = index.schema();
-            let mut parser =
-                QueryParser::for_index(&index, schema.fields().map(|(field, _)| field).collect());
-            for (field, entry) in schema.fields() {
-                if entry.is_indexed() {
-                    if matches!(
-                        entry.field_type(),
-                        FieldType::Str(_) | FieldType::JsonObject(_)
-                    ) {
-                        parser.set_field_fuzzy(field, false, 2, true);
-                    }
-                }
-            }
-            parser
-        };
+        let query_parser = query_parser_options.build(&index)?;
+
+        let committed_next_id = index
+            .load_metas()?
+            .payload
+            .and_then(|payload| payload.parse::<u64>().ok())
+            .unwrap_or(0);
+        let next_id = reader
+            .searcher()
+            .segment_readers()
+            .iter()
+            .filter_map(|segment_reader| segment_reader.fast_fields().u64(ID_FIELD_NAME).ok())
+            .filter(|column| column.num_docs() > 0)
+            .map(|column| column.max_value() + 1)
+            .max()
+            .unwrap_or(0)
+            .max(committed_next_id);
 
         Ok(BurkazIndex(Arc::new(InnerBurkazIndex {
             _name: name,
@@ -110,6 +171,9 @@ impl BurkazIndex {
             reader: reader,
             writer: Arc::new(Mutex::new(writer)),
             query_parser: query_parser,
+            key_field,
+            id_field,
+            next_id: AtomicU64::new(next_id),
         })))
     }
 
@@ -130,6 +194,11 @@ impl BurkazIndex {
         &self.0._name
     }
 
+    #[inline]
+    pub fn underlying_index(&self) -> &Index {
+        &self.0._underlying_index
+    }
+
     pub fn query_parser(&self) -> &QueryParser {
         &self.0.query_parser
     }
@@ -146,77 +215,842 @@ impl BurkazIndex {
         self.0.reader.searcher()
     }
 
+    /// Pins the current view of the index, see [`BurkazSnapshot`].
+    pub fn snapshot(&self) -> BurkazSnapshot {
+        BurkazSnapshot::new(self.searcher())
+    }
+
+    /// Reads the object at `addr` from the latest view of the index. Addresses
+    /// found through a [`BurkazSnapshot`] should be read from the snapshot.
     pub fn get(&self, addr: BurkazObjectAddr) -> crate::Result<TantivyDocument> {
-        let searcher = self.searcher();
-        let max_segment_ord = searcher.segment_readers().len();
-        if addr.segment_ord() >= max_segment_ord as SegmentOrdinal {
-            return Err(BurkazError::ObjectNotFound(addr));
+        get_object(&self.searcher(), addr)
+    }
+
+    /// Reads the objects at `addrs` from a single view of the index, in the same
+    /// order. Addresses without an object give `None`.
+    pub fn get_many(
+        &self,
+        addrs: &[BurkazObjectAddr],
+    ) -> crate::Result<Vec<Option<TantivyDocument>>> {
+        get_objects(&self.searcher(), addrs)
+    }
+
+    /// Returns the object with the given stable id.
+    pub fn get_by_id(&self, id: u64) -> crate::Result<TantivyDocument> {
+        get_object_by_id(&self.searcher(), id)
+    }
+
+    /// Adds the object and returns its stable id.
+    pub fn add(&self, object: TantivyDocument) -> crate::Result<u64> {
+        let ids = self.apply(vec![BurkazWriteOperation::Add(object)])?;
+        Ok(ids[0])
+    }
+
+    /// Adds the objects and returns their stable ids, in the same order.
+    pub fn add_all(&self, objects: Vec<TantivyDocument>) -> crate::Result<Vec<u64>> {
+        if objects.is_empty() {
+            return Ok(Vec::new());
         }
 
-        searcher
-            .doc::<TantivyDocument>(addr.into())
-            .map_err(Into::<BurkazError>::into)
+        self.apply(objects.into_iter().map(BurkazWriteOperation::Add).collect())
     }
 
-    pub fn add(&self, object: TantivyDocument) -> crate::Result<()> {
+    pub fn clear(&self) -> crate::Result<()> {
+        self.apply(vec![BurkazWriteOperation::DeleteAll])
+            .map(|_| ())
+    }
+
+    pub fn delete_all_by_query(&self, query: Box<dyn Query>) -> crate::Result<()> {
         let mut writer = self.get_writer()?;
 
-        writer
-            .add_document(object)
-            .map_err(Into::<BurkazError>::into)?;
+        if let Err(error) = writer.delete_query(query) {
+            writer.rollback().map_err(Into::<BurkazError>::into)?;
+            return Err(error.into());
+        }
 
-        writer.commit().map_err(Into::<BurkazError>::into)?;
+        self.commit(&mut writer)
+    }
 
-        self.0.reader.reload().map_err(Into::<BurkazError>::into)?;
+    /// Replaces the object sharing the same key, or adds it if there is none.
+    /// The written object gets a new stable id, which is returned.
+    pub fn upsert(&self, object: TantivyDocument) -> crate::Result<u64> {
+        let ids = self.apply(vec![BurkazWriteOperation::Upsert(object)])?;
+        Ok(ids[0])
+    }
 
-        Ok(())
+    pub fn delete_by_key(&self, key: BurkazTerm) -> crate::Result<()> {
+        self.apply(vec![BurkazWriteOperation::DeleteByKey(key)])
+            .map(|_| ())
     }
 
-    pub fn add_all(&self, objects: Vec<TantivyDocument>) -> crate::Result<()> {
-        if objects.is_empty() {
-            return Ok(());
+    fn key_field(&self) -> crate::Result<Field> {
+        self.0.key_field.ok_or(BurkazError::InvalidSchema(
+            "index has no unique field".to_owned(),
+        ))
+    }
+
+    fn key_term_of(&self, object: &TantivyDocument) -> crate::Result<Term> {
+        let key_field = self.key_field()?;
+        let key = object
+            .get_first(key_field)
+            .and_then(|value| value.as_leaf())
+            .ok_or(BurkazError::MissingKey)?;
+
+        BurkazTerm::new(key_field.field_id(), &key)?.to_tantivy_term()
+    }
+
+    fn key_term(&self, key: &BurkazTerm) -> crate::Result<Term> {
+        let key_field = self.key_field()?;
+        if key.field_id() != Some(key_field.field_id()) {
+            return Err(BurkazError::InvalidSchema(format!(
+                "term does not target the unique field {}",
+                key_field.field_id()
+            )));
         }
 
-        let mut writer = self.get_writer()?;
+        key.to_tantivy_term()
+    }
 
-        for object in objects.iter() {
-            writer
-                .add_document(object.clone())
-                .map_err(Into::<BurkazError>::into)?;
+    /// Returns the most frequent indexed terms of a text field starting with
+    /// `prefix`, most frequent first.
+    ///
+    /// The prefix goes through the analyzer of the field and its last token is
+    /// completed, so it matches terms the way they were indexed.
+    ///
+    /// Fields using the autocomplete analyzer are rejected, their terms are
+    /// word prefixes rather than words.
+    pub fn suggest(
+        &self,
+        field_id: u32,
+        prefix: &str,
+        limit: usize,
+    ) -> crate::Result<Vec<BurkazSuggestion>> {
+        let searcher = self.searcher();
+        let entry = field_entry(searcher.schema(), field_id)?;
+        let tokenizer = match entry.field_type() {
+            FieldType::Str(options) => options
+                .get_indexing_options()
+                .map(|indexing| indexing.tokenizer()),
+            _ => None,
+        };
+        match tokenizer {
+            None => {
+                return Err(BurkazError::InvalidField(format!(
+                    "cannot suggest terms of field `{}`, it is not an indexed text field",
+                    entry.name()
+                )));
+            }
+            Some(AUTOCOMPLETE_ANALYZER_NAME) => {
+                return Err(BurkazError::InvalidField(format!(
+                    "cannot suggest terms of field `{}`, it indexes word prefixes",
+                    entry.name()
+                )));
+            }
+            Some(_) => {}
+        }
+        if limit == 0 {
+            return Ok(Vec::new());
         }
+        let field = Field::from_field_id(field_id);
 
-        writer.commit().map_err(Into::<BurkazError>::into)?;
+        let mut analyzer = self.underlying_index().tokenizer_for_field(field)?;
+        let mut tokens = analyzer.token_stream(prefix);
+        let mut normalized_prefix = None;
+        while let Some(token) = tokens.next() {
+            normalized_prefix = Some(token.text.clone());
+        }
+        // Prefixes removed by the analyzer, like stop words, are kept as typed.
+        let prefix = normalized_prefix.unwrap_or_else(|| prefix.trim().to_lowercase());
 
-        self.0.reader.reload().map_err(Into::<BurkazError>::into)?;
+        let inverted_indexes = searcher
+            .segment_readers()
+            .iter()
+            .map(|segment_reader| segment_reader.inverted_index(field))
+            .collect::<tantivy::Result<Vec<_>>>()?;
+        let streams = inverted_indexes
+            .iter()
+            .map(|inverted_index| {
+                inverted_index
+                    .terms()
+                    .range()
+                    .ge(prefix.as_bytes())
+                    .into_stream()
+            })
+            .collect::<std::io::Result<Vec<_>>>()
+            .map_err(TantivyError::from)?;
+        let mut terms = TermMerger::new(streams);
 
-        Ok(())
+        // Keeps the `limit` best terms, the worst one on top. Terms come in
+        // order, so a term no more frequent than the worst one never beats it.
+        let mut best = BinaryHeap::<Reverse<(u64, Reverse<String>)>>::with_capacity(limit + 1);
+        while terms.advance() {
+            if !terms.key().starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let Ok(term) = std::str::from_utf8(terms.key()) else {
+                continue;
+            };
+            let doc_freq = terms
+                .current_segment_ords_and_term_infos()
+                .map(|(_, term_info)| u64::from(term_info.doc_freq))
+                .sum::<u64>();
+            if best.len() == limit
+                && best
+                    .peek()
+                    .is_some_and(|Reverse((worst, _))| doc_freq <= *worst)
+            {
+                continue;
+            }
+            best.push(Reverse((doc_freq, Reverse(term.to_owned()))));
+            if best.len() > limit {
+                best.pop();
+            }
+        }
+
+        Ok(best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((doc_freq, Reverse(term)))| BurkazSuggestion { term, doc_freq })
+            .collect())
     }
 
-    pub fn clear(&self) -> crate::Result<()> {
+    pub fn begin_transaction(&self) -> BurkazTransaction {
+        BurkazTransaction::new(self.downgrade())
+    }
+
+    /// Applies the operations in order under a single writer lock and commits
+    /// them together. If any operation fails, the uncommitted changes are
+    /// rolled back and the error is returned.
+    ///
+    /// Returns the stable ids given to the added and upserted objects, in order.
+    /// Operations before the last delete of every object are dropped, their
+    /// objects still get ids so the returned ids stay in order.
+    pub fn apply(&self, operations: Vec<BurkazWriteOperation>) -> crate::Result<Vec<u64>> {
         let mut writer = self.get_writer()?;
+        let mut ids = Vec::new();
+
+        // Deleting every object does not reach the objects still buffered in
+        // the writer, so whatever comes before it is never applied.
+        let first_applied = operations
+            .iter()
+            .rposition(|operation| matches!(operation, BurkazWriteOperation::DeleteAll))
+            .unwrap_or(0);
+
+        for (position, operation) in operations.into_iter().enumerate() {
+            if position < first_applied {
+                if let BurkazWriteOperation::Add(_) | BurkazWriteOperation::Upsert(_) = operation {
+                    ids.push(self.0.next_id.fetch_add(1, Ordering::Relaxed));
+                }
+                continue;
+            }
+
+            let result = match operation {
+                BurkazWriteOperation::Add(object) => {
+                    let object = self.with_next_id(object, &mut ids);
+                    writer.add_document(object).map(|_| ()).map_err(Into::into)
+                }
+                BurkazWriteOperation::DeleteByQuery(query) => query
+                    .to_tantivy_query(self)
+                    .and_then(|query| writer.delete_query(query).map(|_| ()).map_err(Into::into)),
+                BurkazWriteOperation::DeleteAll => writer
+                    .delete_all_documents()
+                    .map(|_| ())
+                    .map_err(Into::into),
+                BurkazWriteOperation::Upsert(object) => {
+                    self.key_term_of(&object).and_then(|key_term| {
+                        writer.delete_term(key_term);
+                        let object = self.with_next_id(object, &mut ids);
+                        writer.add_document(object).map(|_| ()).map_err(Into::into)
+                    })
+                }
+                BurkazWriteOperation::DeleteByKey(key) => self.key_term(&key).map(|key_term| {
+                    writer.delete_term(key_term);
+                }),
+            };
+
+            if let Err(error) = result {
+                writer.rollback().map_err(Into::<BurkazError>::into)?;
+                return Err(error);
+            }
+        }
 
-        writer
-            .delete_all_documents()
-            .map_err(Into::<BurkazError>::into)?;
+        self.commit(&mut writer)?;
 
-        writer.commit().map_err(Into::<BurkazError>::into)?;
+        Ok(ids)
+    }
+
+    fn with_next_id(&self, mut object: TantivyDocument, ids: &mut Vec<u64>) -> TantivyDocument {
+        let id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        object.add_u64(self.0.id_field, id);
+        ids.push(id);
+        object
+    }
+
+    fn commit(&self, writer: &mut IndexWriter<TantivyDocument>) -> crate::Result<()> {
+        let mut prepared_commit = writer.prepare_commit()?;
+        prepared_commit.set_payload(&self.0.next_id.load(Ordering::Relaxed).to_string());
+        prepared_commit.commit()?;
 
         self.0.reader.reload().map_err(Into::<BurkazError>::into)?;
 
         Ok(())
     }
+}
 
-    pub fn delete_all_by_query(&self, query: Box<dyn Query>) -> crate::Result<()> {
-        let mut writer = self.get_writer()?;
+pub(crate) fn get_object(
+    searcher: &Searcher,
+    addr: BurkazObjectAddr,
+) -> crate::Result<TantivyDocument> {
+    find_object(searcher, addr)?.ok_or(BurkazError::ObjectNotFound(addr))
+}
 
-        writer
-            .delete_query(query)
-            .map_err(Into::<BurkazError>::into)?;
+pub(crate) fn get_objects(
+    searcher: &Searcher,
+    addrs: &[BurkazObjectAddr],
+) -> crate::Result<Vec<Option<TantivyDocument>>> {
+    addrs
+        .iter()
+        .map(|addr| find_object(searcher, *addr))
+        .collect()
+}
 
-        writer.commit().map_err(Into::<BurkazError>::into)?;
+/// Returns `None` when the address points past the objects of the searcher or
+/// to a deleted object.
+fn find_object(
+    searcher: &Searcher,
+    addr: BurkazObjectAddr,
+) -> crate::Result<Option<TantivyDocument>> {
+    let max_segment_ord = searcher.segment_readers().len();
+    if addr.segment_ord() >= max_segment_ord as SegmentOrdinal {
+        return Ok(None);
+    }
 
-        self.0.reader.reload().map_err(Into::<BurkazError>::into)?;
+    let segment_reader = searcher.segment_reader(addr.segment_ord());
+    if addr.doc_id() >= segment_reader.max_doc() || segment_reader.is_deleted(addr.doc_id()) {
+        return Ok(None);
+    }
 
-        Ok(())
+    searcher
+        .doc::<TantivyDocument>(addr.into())
+        .map(Some)
+        .map_err(Into::<BurkazError>::into)
+}
+
+pub(crate) fn get_object_by_id(searcher: &Searcher, id: u64) -> crate::Result<TantivyDocument> {
+    let id_field = searcher.schema().get_field(ID_FIELD_NAME)?;
+    let query = TermQuery::new(Term::from_field_u64(id_field, id), IndexRecordOption::Basic);
+    let (_, addr) = searcher
+        .search(&query, &TopDocs::with_limit(1))?
+        .pop()
+        .ok_or(BurkazError::ObjectIdNotFound(id))?;
+
+    searcher
+        .doc::<TantivyDocument>(addr)
+        .map_err(Into::<BurkazError>::into)
+}
+
+/// Fails when a field references a tokenizer that is neither built in nor
+/// registered by an analyzer of the schema.
+fn validate_tokenizers(index: &Index) -> crate::Result<()> {
+    let schema = index.schema();
+    for (_, field_entry) in schema.fields() {
+        let (indexing_tokenizer, fast_tokenizer) = match field_entry.field_type() {
+            FieldType::Str(options) => (
+                options
+                    .get_indexing_options()
+                    .map(|indexing| indexing.tokenizer()),
+                options.get_fast_field_tokenizer_name(),
+            ),
+            FieldType::JsonObject(options) => (
+                options
+                    .get_text_indexing_options()
+                    .map(|indexing| indexing.tokenizer()),
+                options.get_fast_field_tokenizer_name(),
+            ),
+            _ => continue,
+        };
+
+        let unknown = indexing_tokenizer
+            .filter(|name| index.tokenizers().get(name).is_none())
+            .or(fast_tokenizer.filter(|name| index.fast_field_tokenizer().get(name).is_none()));
+        if let Some(name) = unknown {
+            return Err(BurkazError::InvalidSchema(format!(
+                "field `{}` uses unknown tokenizer `{}`",
+                field_entry.name(),
+                name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reads the stable id of the object at `addr` in the given searcher.
+pub(crate) fn object_id(searcher: &Searcher, addr: DocAddress) -> crate::Result<u64> {
+    if addr.segment_ord as usize >= searcher.segment_readers().len() {
+        return Err(BurkazError::ObjectNotFound(addr.into()));
+    }
+
+    searcher
+        .segment_reader(addr.segment_ord)
+        .fast_fields()
+        .u64(ID_FIELD_NAME)?
+        .first(addr.doc_id)
+        .ok_or_else(|| BurkazError::ObjectNotFound(addr.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        analyzer::{BurkazTokenFilter, BurkazTokenizer},
+        query::BurkazQuery,
+        query_runner::QueryRunner,
+        schema::{BurkazSchemaField, BurkazSchemaFieldOptions, BurkazSchemaFieldType},
+    };
+
+    fn field_options(typ: BurkazSchemaFieldType, unique: bool) -> BurkazSchemaFieldOptions {
+        BurkazSchemaFieldOptions {
+            unique,
+            ..BurkazSchemaFieldOptions::for_test(typ)
+        }
+    }
+
+    fn keyed_index() -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "sku".to_owned(),
+            field_options(BurkazSchemaFieldType::Text, true),
+        ));
+        schema.add_field(BurkazSchemaField::new(
+            "stock".to_owned(),
+            field_options(BurkazSchemaFieldType::Int64, false),
+        ));
+        BurkazIndex::new("products".to_owned(), schema, BurkazDirectory::InMemory).unwrap()
+    }
+
+    fn product(sku: &str, stock: i64) -> TantivyDocument {
+        let mut object = TantivyDocument::new();
+        object.add_text(Field::from_field_id(0), sku);
+        object.add_i64(Field::from_field_id(1), stock);
+        object
+    }
+
+    fn stocks(index: &BurkazIndex) -> Vec<i64> {
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+        let mut stocks = runner
+            .search(0, 10)
+            .unwrap()
+            .into_iter()
+            .map(|addr| {
+                index
+                    .get(addr)
+                    .unwrap()
+                    .get_first(Field::from_field_id(1))
+                    .and_then(|value| value.as_i64())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        stocks.sort();
+        stocks
+    }
+
+    #[test]
+    fn upsert_replaces_object_with_same_key() {
+        let index = keyed_index();
+
+        index.upsert(product("AB-1 blue", 1)).unwrap();
+        index.upsert(product("AB-2", 2)).unwrap();
+        index.upsert(product("AB-1 blue", 3)).unwrap();
+
+        assert_eq!(stocks(&index), vec![2, 3]);
+    }
+
+    #[test]
+    fn delete_by_key_removes_object() {
+        let index = keyed_index();
+        index
+            .add_all(vec![product("AB-1", 1), product("AB-2", 2)])
+            .unwrap();
+
+        index
+            .delete_by_key(BurkazTerm::new(0, &"AB-1".into()).unwrap())
+            .unwrap();
+
+        assert_eq!(stocks(&index), vec![2]);
+        assert!(matches!(
+            index.delete_by_key(BurkazTerm::new(1, &2i64.into()).unwrap()),
+            Err(BurkazError::InvalidSchema(_))
+        ));
+    }
+
+    #[test]
+    fn upsert_requires_key() {
+        let index = keyed_index();
+
+        let mut object = TantivyDocument::new();
+        object.add_i64(Field::from_field_id(1), 1);
+
+        assert!(matches!(index.upsert(object), Err(BurkazError::MissingKey)));
+        assert_eq!(stocks(&index), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn only_one_unique_field_is_allowed() {
+        let mut schema = BurkazSchema::default();
+        for name in ["a", "b"] {
+            schema.add_field(BurkazSchemaField::new(
+                name.to_owned(),
+                field_options(BurkazSchemaFieldType::Int64, true),
+            ));
+        }
+
+        assert!(matches!(
+            BurkazIndex::new("invalid".to_owned(), schema, BurkazDirectory::InMemory),
+            Err(BurkazError::InvalidSchema(_))
+        ));
+    }
+
+    #[test]
+    fn unique_text_field_must_not_be_tokenized() {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "sku".to_owned(),
+            BurkazSchemaFieldOptions {
+                indexing_tokenizer: Some("default".to_owned()),
+                ..field_options(BurkazSchemaFieldType::Text, true)
+            },
+        ));
+
+        assert!(matches!(
+            BurkazIndex::new("invalid".to_owned(), schema, BurkazDirectory::InMemory),
+            Err(BurkazError::InvalidSchema(_))
+        ));
+
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "sku".to_owned(),
+            BurkazSchemaFieldOptions {
+                indexing_tokenizer: Some("raw".to_owned()),
+                ..field_options(BurkazSchemaFieldType::Text, true)
+            },
+        ));
+        let index =
+            BurkazIndex::new("products".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+        let sku = |sku: &str| {
+            let mut object = TantivyDocument::new();
+            object.add_text(Field::from_field_id(0), sku);
+            object
+        };
+        index.upsert(sku("AB 1")).unwrap();
+        index.upsert(sku("AB 1")).unwrap();
+        assert_eq!(
+            QueryRunner::new(index.downgrade(), BurkazQuery::All)
+                .count()
+                .unwrap(),
+            1
+        );
+    }
+
+    fn stock_of(object: &TantivyDocument) -> i64 {
+        object
+            .get_first(Field::from_field_id(1))
+            .and_then(|value| value.as_i64())
+            .unwrap()
+    }
+
+    #[test]
+    fn ids_survive_segment_merges() {
+        let index = keyed_index();
+        let first = index.add(product("AB-1", 1)).unwrap();
+        let others = index
+            .add_all(vec![product("AB-2", 2), product("AB-3", 3)])
+            .unwrap();
+        assert_eq!(first, 0);
+        assert_eq!(others, vec![1, 2]);
+
+        let upserted = index.upsert(product("AB-1", 4)).unwrap();
+        assert_eq!(upserted, 3);
+
+        let segment_ids = index.underlying_index().searchable_segment_ids().unwrap();
+        assert!(segment_ids.len() > 1);
+        {
+            let mut writer = index.get_writer().unwrap();
+            writer.merge(&segment_ids).wait().unwrap();
+            writer.commit().unwrap();
+        }
+        index.0.reader.reload().unwrap();
+        assert_eq!(index.searcher().segment_readers().len(), 1);
+
+        assert_eq!(stock_of(&index.get_by_id(2).unwrap()), 3);
+        assert_eq!(stock_of(&index.get_by_id(upserted).unwrap()), 4);
+        assert!(matches!(
+            index.get_by_id(first),
+            Err(BurkazError::ObjectIdNotFound(0))
+        ));
+
+        let mut ids = QueryRunner::new(index.downgrade(), BurkazQuery::All)
+            .search_ids(0, 10)
+            .unwrap();
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(
+            QueryRunner::new(index.downgrade(), BurkazQuery::All)
+                .search_ids(0, 0)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("burkaz-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    fn on_disk_keyed_index(path: &Path) -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "sku".to_owned(),
+            field_options(BurkazSchemaFieldType::Text, true),
+        ));
+        schema.add_field(BurkazSchemaField::new(
+            "stock".to_owned(),
+            field_options(BurkazSchemaFieldType::Int64, false),
+        ));
+        BurkazIndex::new("products".to_owned(), schema, BurkazDirectory::OnDisk(path)).unwrap()
+    }
+
+    #[test]
+    fn ids_are_not_reused_after_reopening() {
+        let path = temp_dir("reopen");
+
+        let index = on_disk_keyed_index(&path);
+        let ids = index
+            .add_all(vec![product("AB-1", 1), product("AB-2", 2)])
+            .unwrap();
+        assert_eq!(index.add(product("AB-3", 3)).unwrap(), 2);
+        index
+            .delete_by_key(BurkazTerm::new(0, &"AB-3".into()).unwrap())
+            .unwrap();
+        let segment_ids = index.underlying_index().searchable_segment_ids().unwrap();
+        index
+            .get_writer()
+            .unwrap()
+            .merge(&segment_ids)
+            .wait()
+            .unwrap();
+        drop(index);
+
+        let index = on_disk_keyed_index(&path);
+        assert_eq!(index.searcher().num_docs(), 2);
+        assert_eq!(index.add(product("AB-4", 4)).unwrap(), 3);
+        assert_eq!(stock_of(&index.get_by_id(ids[1]).unwrap()), 2);
+        drop(index);
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn indexes_without_ids_are_rejected() {
+        let path = temp_dir("without-ids");
+        let mut schema = Schema::builder();
+        schema.add_text_field("sku", tantivy::schema::STRING);
+        schema.add_i64_field("stock", tantivy::schema::STORED);
+        Index::create_in_dir(&path, schema.build()).unwrap();
+
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "sku".to_owned(),
+            field_options(BurkazSchemaFieldType::Text, false),
+        ));
+        let error = BurkazIndex::new(
+            "products".to_owned(),
+            schema,
+            BurkazDirectory::OnDisk(&path),
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(error, BurkazError::InvalidSchema(_)));
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn id_field_name_is_reserved() {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            ID_FIELD_NAME.to_owned(),
+            field_options(BurkazSchemaFieldType::Int64, false),
+        ));
+
+        assert!(matches!(
+            BurkazIndex::new("invalid".to_owned(), schema, BurkazDirectory::InMemory),
+            Err(BurkazError::InvalidSchema(_))
+        ));
+    }
+
+    #[test]
+    fn get_many_reports_missing_objects() {
+        let index = keyed_index();
+        index
+            .add_all(vec![product("AB-1", 1), product("AB-2", 2)])
+            .unwrap();
+        let mut addrs = QueryRunner::new(index.downgrade(), BurkazQuery::All)
+            .search(0, 10)
+            .unwrap();
+        addrs.sort_by_key(|addr| addr.val());
+
+        index
+            .delete_by_key(BurkazTerm::new(0, &"AB-1".into()).unwrap())
+            .unwrap();
+        addrs.push(BurkazObjectAddr::from(99u64 << 32));
+        addrs.push(BurkazObjectAddr::from(99u64));
+
+        let objects = index.get_many(&addrs).unwrap();
+        let stocks = objects
+            .iter()
+            .map(|object| object.as_ref().map(stock_of))
+            .collect::<Vec<_>>();
+        assert_eq!(stocks, vec![None, Some(2), None, None]);
+    }
+
+    #[test]
+    fn schema_analyzers_are_registered() {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "title".to_owned(),
+            BurkazSchemaFieldOptions {
+                indexing_tokenizer: Some("folded".to_owned()),
+                ..field_options(BurkazSchemaFieldType::Text, false)
+            },
+        ));
+        schema.add_analyzer(BurkazAnalyzer::new(
+            "folded".to_owned(),
+            BurkazTokenizer::Simple,
+            vec![
+                BurkazTokenFilter::LowerCase,
+                BurkazTokenFilter::AsciiFolding,
+            ],
+        ));
+        let index =
+            BurkazIndex::new("titles".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+
+        let mut object = TantivyDocument::new();
+        object.add_text(Field::from_field_id(0), "Café Crème");
+        index.add(object).unwrap();
+
+        let query = BurkazQuery::Parse {
+            query_text: "CAFE creme".to_owned(),
+            lenient: false,
+            options: None,
+        };
+        assert_eq!(
+            QueryRunner::new(index.downgrade(), query).count().unwrap(),
+            1
+        );
+
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "title".to_owned(),
+            BurkazSchemaFieldOptions {
+                indexing_tokenizer: Some("turkish".to_owned()),
+                ..field_options(BurkazSchemaFieldType::Text, false)
+            },
+        ));
+        let index =
+            BurkazIndex::new("titles".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+        let mut object = TantivyDocument::new();
+        object.add_text(Field::from_field_id(0), "Kırmızı çiçekler");
+        index.add(object).unwrap();
+        let query = BurkazQuery::Parse {
+            query_text: "cicek".to_owned(),
+            lenient: false,
+            options: None,
+        };
+        assert_eq!(
+            QueryRunner::new(index.downgrade(), query).count().unwrap(),
+            1
+        );
+
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "title".to_owned(),
+            BurkazSchemaFieldOptions {
+                indexing_tokenizer: Some("missing".to_owned()),
+                ..field_options(BurkazSchemaFieldType::Text, false)
+            },
+        ));
+        assert!(matches!(
+            BurkazIndex::new("invalid".to_owned(), schema, BurkazDirectory::InMemory),
+            Err(BurkazError::InvalidSchema(_))
+        ));
+    }
+
+    #[test]
+    fn suggest_completes_frequent_terms() {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "title".to_owned(),
+            field_options(BurkazSchemaFieldType::Text, false),
+        ));
+        schema.add_field(BurkazSchemaField::new(
+            "stock".to_owned(),
+            field_options(BurkazSchemaFieldType::Int64, false),
+        ));
+        schema.add_field(BurkazSchemaField::new(
+            "name".to_owned(),
+            BurkazSchemaFieldOptions {
+                indexing_tokenizer: Some(AUTOCOMPLETE_ANALYZER_NAME.to_owned()),
+                ..BurkazSchemaFieldOptions::for_test(BurkazSchemaFieldType::Text)
+            },
+        ));
+        let index =
+            BurkazIndex::new("titles".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+        let title = |title: &str| {
+            let mut object = TantivyDocument::new();
+            object.add_text(Field::from_field_id(0), title);
+            object
+        };
+        index
+            .add_all(vec![title("Apple iPhone"), title("Apple iPad")])
+            .unwrap();
+        index
+            .add_all(vec![
+                title("Apricot jam"),
+                title("apple pie"),
+                title("Banana"),
+            ])
+            .unwrap();
+
+        let suggestions = |prefix: &str, limit: usize| {
+            index
+                .suggest(0, prefix, limit)
+                .unwrap()
+                .into_iter()
+                .map(|suggestion| (suggestion.term, suggestion.doc_freq))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            suggestions("AP", 2),
+            vec![("apple".to_owned(), 3), ("apricot".to_owned(), 1)]
+        );
+        assert_eq!(
+            suggestions("red ip", 10),
+            vec![("ipad".to_owned(), 1), ("iphone".to_owned(), 1)]
+        );
+        assert!(suggestions("cherry", 10).is_empty());
+
+        assert_eq!(suggestions("ip", 1), vec![("ipad".to_owned(), 1)]);
+        assert!(suggestions("ap", 0).is_empty());
+
+        assert!(matches!(
+            index.suggest(1, "1", 10),
+            Err(BurkazError::InvalidField(_))
+        ));
+        assert!(matches!(
+            index.suggest(2, "ap", 10),
+            Err(BurkazError::InvalidField(_))
+        ));
     }
 }
@@ -2,12 +2,19 @@
 mod error;
 
 mod address;
+mod aggregation;
+mod analyzer;
+mod cursor;
 mod index;
 mod object;
 mod query;
+mod query_parser;
 mod query_runner;
 mod schema;
+mod snapshot;
+mod spelling;
 mod term;
+mod transaction;
 
 mod native;
 
@@ -1,9 +1,11 @@
 #[macro_use]
 mod native_index;
+mod native_aggregation;
 mod native_object;
 mod native_schema;
 #[macro_use]
 mod native_query;
+mod native_query_parser;
 mod native_query_runner;
 #[macro_use]
 mod native_term;
@@ -0,0 +1,356 @@
+use std::ffi::{CString, c_char};
+
+use crate::{
+    aggregation::{
+        BurkazAggregation, BurkazAggregationResult, BurkazBucketKey, BurkazHistogramAggregation,
+        BurkazHistogramAggregationResult, BurkazMetricsAggregation, BurkazMetricsAggregationResult,
+        BurkazTermsAggregation, BurkazTermsAggregationResult,
+    },
+    object::ValueType,
+    slice_from_ptr,
+};
+
+fn into_raw_parts<T>(values: Vec<T>) -> (*mut T, usize) {
+    if values.is_empty() {
+        return (std::ptr::null_mut(), 0);
+    }
+    let len = values.len();
+    (Box::into_raw(values.into_boxed_slice()).cast(), len)
+}
+
+fn drop_raw_parts<T>(ptr: *mut T, len: usize) {
+    if !ptr.is_null() {
+        drop(unsafe { Vec::from_raw_parts(ptr, len, len) });
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazTermsAggregation {
+    pub field_id: u32,
+    pub size: u32,
+    pub min_doc_count: u64,
+}
+
+#[repr(C)]
+pub struct CBurkazMetricsAggregation {
+    pub field_id: u32,
+    /// Percents between `0` and `100`, a null array skips the percentiles.
+    pub percent_arr_ptr: *const f64,
+    pub percent_arr_len: usize,
+}
+
+/// Values of date fields are given in microseconds since the Unix epoch.
+#[repr(C)]
+pub struct CBurkazHistogramAggregation {
+    pub field_id: u32,
+    pub interval: f64,
+    pub min_doc_count: u64,
+    pub has_extended_bounds: u8,
+    pub extended_bounds_min: f64,
+    pub extended_bounds_max: f64,
+    pub sub_aggregation_arr_ptr: *const CBurkazAggregation,
+    pub sub_aggregation_arr_len: usize,
+}
+
+/// Aggregation types: `1` terms, `2` metrics, `3` histogram. Only the member
+/// matching `typ` is read.
+#[repr(C)]
+pub struct CBurkazAggregation {
+    pub typ: u8,
+    pub terms: CBurkazTermsAggregation,
+    pub metrics: CBurkazMetricsAggregation,
+    pub histogram_ptr: *const CBurkazHistogramAggregation,
+}
+
+pub fn terms_aggregation_from_native(c_terms: &CBurkazTermsAggregation) -> BurkazTermsAggregation {
+    BurkazTermsAggregation {
+        field_id: c_terms.field_id,
+        size: c_terms.size,
+        min_doc_count: c_terms.min_doc_count,
+    }
+}
+
+pub fn metrics_aggregation_from_native(
+    c_metrics: &CBurkazMetricsAggregation,
+) -> BurkazMetricsAggregation {
+    BurkazMetricsAggregation {
+        field_id: c_metrics.field_id,
+        percents: slice_from_ptr!(c_metrics.percent_arr_ptr, c_metrics.percent_arr_len).to_vec(),
+    }
+}
+
+/// Returns `None` when a sub-aggregation has an unknown type.
+pub fn histogram_aggregation_from_native(
+    c_histogram: &CBurkazHistogramAggregation,
+) -> Option<BurkazHistogramAggregation> {
+    let sub_aggregations = slice_from_ptr!(
+        c_histogram.sub_aggregation_arr_ptr,
+        c_histogram.sub_aggregation_arr_len
+    )
+    .iter()
+    .map(aggregation_from_native)
+    .collect::<Option<_>>()?;
+
+    Some(BurkazHistogramAggregation {
+        field_id: c_histogram.field_id,
+        interval: c_histogram.interval,
+        min_doc_count: c_histogram.min_doc_count,
+        extended_bounds: (c_histogram.has_extended_bounds != 0).then_some((
+            c_histogram.extended_bounds_min,
+            c_histogram.extended_bounds_max,
+        )),
+        sub_aggregations,
+    })
+}
+
+pub fn aggregation_from_native(c_aggregation: &CBurkazAggregation) -> Option<BurkazAggregation> {
+    match c_aggregation.typ {
+        1 => Some(BurkazAggregation::Terms(terms_aggregation_from_native(
+            &c_aggregation.terms,
+        ))),
+        2 => Some(BurkazAggregation::Metrics(metrics_aggregation_from_native(
+            &c_aggregation.metrics,
+        ))),
+        3 if !c_aggregation.histogram_ptr.is_null() => {
+            histogram_aggregation_from_native(unsafe { &*c_aggregation.histogram_ptr })
+                .map(BurkazAggregation::Histogram)
+        }
+        _ => None,
+    }
+}
+
+/// Key of an aggregation bucket. `typ` holds the object value type code and
+/// only the matching value is set, text keys are owned by the bucket.
+#[repr(C)]
+pub struct CBurkazBucketKey {
+    pub typ: u8,
+    pub int_value: i64,
+    pub u64_value: u64,
+    pub f64_value: f64,
+    pub bool_value: bool,
+    pub text_ptr: *mut c_char,
+}
+
+impl From<BurkazBucketKey> for CBurkazBucketKey {
+    fn from(key: BurkazBucketKey) -> Self {
+        let mut c_key = CBurkazBucketKey {
+            typ: ValueType::Null as u8,
+            int_value: 0,
+            u64_value: 0,
+            f64_value: 0.0,
+            bool_value: false,
+            text_ptr: std::ptr::null_mut(),
+        };
+        match key {
+            BurkazBucketKey::Int64(value) => {
+                c_key.typ = ValueType::Int64 as u8;
+                c_key.int_value = value;
+            }
+            BurkazBucketKey::Text(value) => {
+                c_key.typ = ValueType::Text as u8;
+                c_key.text_ptr =
+                    CString::new(value).map_or(std::ptr::null_mut(), CString::into_raw);
+            }
+            BurkazBucketKey::Boolean(value) => {
+                c_key.typ = ValueType::Boolean as u8;
+                c_key.bool_value = value;
+            }
+            BurkazBucketKey::F64(value) => {
+                c_key.typ = ValueType::F64 as u8;
+                c_key.f64_value = value;
+            }
+            BurkazBucketKey::U64(value) => {
+                c_key.typ = ValueType::U64 as u8;
+                c_key.u64_value = value;
+            }
+        }
+        c_key
+    }
+}
+
+impl Drop for CBurkazBucketKey {
+    fn drop(&mut self) {
+        if !self.text_ptr.is_null() {
+            drop(unsafe { CString::from_raw(self.text_ptr) });
+        }
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazTermsBucket {
+    pub key: CBurkazBucketKey,
+    pub doc_count: u64,
+}
+
+#[repr(C)]
+pub struct CBurkazTermsAggregationResult {
+    pub bucket_arr_ptr: *mut CBurkazTermsBucket,
+    pub bucket_arr_len: usize,
+    pub sum_other_doc_count: u64,
+}
+
+impl From<BurkazTermsAggregationResult> for CBurkazTermsAggregationResult {
+    fn from(result: BurkazTermsAggregationResult) -> Self {
+        let buckets = result
+            .buckets
+            .into_iter()
+            .map(|bucket| CBurkazTermsBucket {
+                key: bucket.key.into(),
+                doc_count: bucket.doc_count,
+            })
+            .collect();
+        let (bucket_arr_ptr, bucket_arr_len) = into_raw_parts(buckets);
+        Self {
+            bucket_arr_ptr,
+            bucket_arr_len,
+            sum_other_doc_count: result.sum_other_doc_count,
+        }
+    }
+}
+
+impl Drop for CBurkazTermsAggregationResult {
+    fn drop(&mut self) {
+        drop_raw_parts(self.bucket_arr_ptr, self.bucket_arr_len);
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazPercentile {
+    pub percent: f64,
+    /// `NaN` when no object has a value.
+    pub value: f64,
+}
+
+/// `min`, `max` and `avg` are `NaN` when no object has a value.
+#[repr(C)]
+pub struct CBurkazMetricsAggregationResult {
+    pub count: u64,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    pub percentile_arr_ptr: *mut CBurkazPercentile,
+    pub percentile_arr_len: usize,
+}
+
+impl From<BurkazMetricsAggregationResult> for CBurkazMetricsAggregationResult {
+    fn from(result: BurkazMetricsAggregationResult) -> Self {
+        let percentiles = result
+            .percentiles
+            .into_iter()
+            .map(|percentile| CBurkazPercentile {
+                percent: percentile.percent,
+                value: percentile.value.unwrap_or(f64::NAN),
+            })
+            .collect();
+        let (percentile_arr_ptr, percentile_arr_len) = into_raw_parts(percentiles);
+        Self {
+            count: result.count,
+            sum: result.sum,
+            min: result.min.unwrap_or(f64::NAN),
+            max: result.max.unwrap_or(f64::NAN),
+            avg: result.avg.unwrap_or(f64::NAN),
+            percentile_arr_ptr,
+            percentile_arr_len,
+        }
+    }
+}
+
+impl Drop for CBurkazMetricsAggregationResult {
+    fn drop(&mut self) {
+        drop_raw_parts(self.percentile_arr_ptr, self.percentile_arr_len);
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazHistogramBucket {
+    /// Start of the bucket range.
+    pub key: f64,
+    pub doc_count: u64,
+    /// Results of the sub-aggregations, in the order they were requested.
+    pub sub_aggregation_arr_ptr: *mut CBurkazAggregationResult,
+    pub sub_aggregation_arr_len: usize,
+}
+
+impl Drop for CBurkazHistogramBucket {
+    fn drop(&mut self) {
+        drop_raw_parts(self.sub_aggregation_arr_ptr, self.sub_aggregation_arr_len);
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazHistogramAggregationResult {
+    pub bucket_arr_ptr: *mut CBurkazHistogramBucket,
+    pub bucket_arr_len: usize,
+}
+
+impl From<BurkazHistogramAggregationResult> for CBurkazHistogramAggregationResult {
+    fn from(result: BurkazHistogramAggregationResult) -> Self {
+        let buckets = result
+            .buckets
+            .into_iter()
+            .map(|bucket| {
+                let sub_aggregations = bucket
+                    .sub_aggregations
+                    .into_iter()
+                    .map(CBurkazAggregationResult::from)
+                    .collect();
+                let (sub_aggregation_arr_ptr, sub_aggregation_arr_len) =
+                    into_raw_parts(sub_aggregations);
+                CBurkazHistogramBucket {
+                    key: bucket.key,
+                    doc_count: bucket.doc_count,
+                    sub_aggregation_arr_ptr,
+                    sub_aggregation_arr_len,
+                }
+            })
+            .collect();
+        let (bucket_arr_ptr, bucket_arr_len) = into_raw_parts(buckets);
+        Self {
+            bucket_arr_ptr,
+            bucket_arr_len,
+        }
+    }
+}
+
+impl Drop for CBurkazHistogramAggregationResult {
+    fn drop(&mut self) {
+        drop_raw_parts(self.bucket_arr_ptr, self.bucket_arr_len);
+    }
+}
+
+/// Result of a sub-aggregation, only the member matching `typ` is set. Types
+/// are the same as in [`CBurkazAggregation`].
+#[repr(C)]
+pub struct CBurkazAggregationResult {
+    pub typ: u8,
+    pub terms: CBurkazTermsAggregationResult,
+    pub metrics: CBurkazMetricsAggregationResult,
+    pub histogram: CBurkazHistogramAggregationResult,
+}
+
+impl From<BurkazAggregationResult> for CBurkazAggregationResult {
+    fn from(result: BurkazAggregationResult) -> Self {
+        let mut c_result = CBurkazAggregationResult {
+            typ: 0,
+            terms: BurkazTermsAggregationResult::default().into(),
+            metrics: BurkazMetricsAggregationResult::default().into(),
+            histogram: BurkazHistogramAggregationResult::default().into(),
+        };
+        match result {
+            BurkazAggregationResult::Terms(terms) => {
+                c_result.typ = 1;
+                c_result.terms = terms.into();
+            }
+            BurkazAggregationResult::Metrics(metrics) => {
+                c_result.typ = 2;
+                c_result.metrics = metrics.into();
+            }
+            BurkazAggregationResult::Histogram(histogram) => {
+                c_result.typ = 3;
+                c_result.histogram = histogram.into();
+            }
+        }
+        c_result
+    }
+}
@@ -5,3 +5,15 @@ macro_rules! str_from_ptr {
         unsafe { str::from_utf8_unchecked(std::slice::from_raw_parts($ptr.cast(), $len)) }
     };
 }
+
+#[macro_export]
+macro_rules! slice_from_ptr {
+    ($ptr:expr, $len:expr) => {{
+        let (ptr, len) = ($ptr, $len);
+        if ptr.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(ptr, len) }
+        }
+    }};
+}
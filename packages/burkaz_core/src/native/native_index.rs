@@ -6,10 +6,16 @@ use std::{
 use tantivy::TantivyDocument;
 
 use crate::{
+    address::BurkazObjectAddr,
     error::BurkazError,
     index::{BurkazDirectory, BurkazIndex},
+    query_from_ptr,
     schema::BurkazSchema,
-    str_from_ptr,
+    slice_from_ptr,
+    snapshot::BurkazSnapshot,
+    spelling::BurkazSpellCorrection,
+    str_from_ptr, term_from_ptr,
+    transaction::BurkazTransaction,
 };
 
 #[macro_export]
@@ -19,6 +25,26 @@ macro_rules! index_from_ptr {
     };
 }
 
+/// Returns a `NullPointer` error from the enclosing `catch_error!` block when
+/// the pointer is null.
+macro_rules! snapshot_from_ptr {
+    ($snapshot_ptr:expr) => {{
+        let snapshot_ptr = $snapshot_ptr as *const crate::snapshot::BurkazSnapshot;
+        if snapshot_ptr.is_null() {
+            return Err(crate::error::BurkazError::NullPointer(
+                "snapshot pointer is null",
+            ));
+        }
+        unsafe { &*snapshot_ptr }
+    }};
+}
+
+macro_rules! transaction_from_ptr {
+    ($transaction_ptr:expr) => {
+        unsafe { &mut *($transaction_ptr as *mut crate::transaction::BurkazTransaction) }
+    };
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn burkaz_index_open(
     name_ptr: *const c_char,
@@ -96,22 +122,35 @@ pub extern "C" fn burkaz_free_string(string_ptr: *const c_char) {
     }
 }
 
+/// Writes the stable id of the added object to `id_ptr`, unless it is null.
 #[unsafe(no_mangle)]
-pub extern "C" fn burkaz_index_add(index_ptr: *const c_void, object_ptr: *const c_void) -> u8 {
+pub extern "C" fn burkaz_index_add(
+    index_ptr: *const c_void,
+    object_ptr: *const c_void,
+    id_ptr: *mut u64,
+) -> u8 {
     let object = unsafe { *Box::<TantivyDocument>::from_raw(object_ptr as *mut _) };
 
     catch_error!({
         let index = index_from_ptr!(index_ptr);
-        let result = index.add(object);
-        result
+        let id = index.add(object)?;
+        if !id_ptr.is_null() {
+            unsafe {
+                *id_ptr = id;
+            }
+        }
+        ok!()
     })
 }
 
+/// Writes the stable ids of the added objects to `id_array_ptr`, unless it is
+/// null. It must have room for `object_array_len` ids.
 #[unsafe(no_mangle)]
 pub extern "C" fn burkaz_index_add_all(
     index_ptr: *const c_void,
     object_array_ptr: *const *const c_void,
     object_array_len: usize,
+    id_array_ptr: *mut u64,
 ) -> u8 {
     catch_error!({
         let objects = unsafe {
@@ -126,7 +165,53 @@ pub extern "C" fn burkaz_index_add_all(
         };
 
         let index = index_from_ptr!(index_ptr);
-        index.add_all(objects)
+        let ids = index.add_all(objects)?;
+        if !id_array_ptr.is_null() {
+            unsafe {
+                std::slice::from_raw_parts_mut(id_array_ptr, ids.len()).copy_from_slice(&ids);
+            }
+        }
+        ok!()
+    })
+}
+
+/// Writes the stable id of the written object to `id_ptr`, unless it is null.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_index_upsert(
+    index_ptr: *const c_void,
+    object_ptr: *const c_void,
+    id_ptr: *mut u64,
+) -> u8 {
+    catch_error!({
+        if object_ptr.is_null() {
+            return Err(BurkazError::NullPointer("object pointer is null"));
+        }
+        let object = unsafe { *Box::<TantivyDocument>::from_raw(object_ptr as *mut _) };
+
+        let index = index_from_ptr!(index_ptr);
+        let id = index.upsert(object)?;
+        if !id_ptr.is_null() {
+            unsafe {
+                *id_ptr = id;
+            }
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_index_delete_by_key(
+    index_ptr: *const c_void,
+    term_ptr: *const c_void,
+) -> u8 {
+    catch_error!({
+        if term_ptr.is_null() {
+            return Err(BurkazError::NullPointer("term pointer is null"));
+        }
+        let key = term_from_ptr!(term_ptr);
+
+        let index = index_from_ptr!(index_ptr);
+        index.delete_by_key(key)
     })
 }
 
@@ -157,3 +242,576 @@ pub extern "C" fn burkaz_index_get(
         ok!()
     })
 }
+
+/// Reads the objects at the addresses from a single view of the index. The
+/// result array has one entry per address, null when there is no object there.
+///
+/// The objects are owned by the caller and released with `burkaz_free_object`,
+/// the array with `burkaz_free_object_array`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_index_get_many(
+    index_ptr: *const c_void,
+    addr_arr_ptr: *const u64,
+    addr_arr_len: usize,
+    object_arr_ptr: *mut *const *const c_void,
+) -> u8 {
+    catch_error!({
+        let index = index_from_ptr!(index_ptr);
+        let objects = index.get_many(&addrs_from_ptr(addr_arr_ptr, addr_arr_len))?;
+
+        unsafe {
+            *object_arr_ptr = objects_into_raw(objects);
+        }
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_object_array(
+    object_arr_ptr: *const *const c_void,
+    object_arr_len: usize,
+) {
+    if !object_arr_ptr.is_null() {
+        drop(unsafe {
+            Vec::from_raw_parts(object_arr_ptr.cast_mut(), object_arr_len, object_arr_len)
+        });
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazSuggestion {
+    pub term_ptr: *mut c_char,
+    pub doc_freq: u64,
+}
+
+impl Drop for CBurkazSuggestion {
+    fn drop(&mut self) {
+        if !self.term_ptr.is_null() {
+            drop(unsafe { CString::from_raw(self.term_ptr) });
+        }
+    }
+}
+
+/// Completes `prefix` with the most frequent terms of a text field.
+///
+/// The suggestions are released with `burkaz_free_suggestions`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_index_suggest(
+    index_ptr: *const c_void,
+    field_id: u32,
+    prefix_ptr: *const c_char,
+    prefix_len: usize,
+    limit: usize,
+    suggestion_arr_ptr: *mut *const CBurkazSuggestion,
+    suggestion_arr_len_ptr: *mut usize,
+) -> u8 {
+    catch_error!({
+        let index = index_from_ptr!(index_ptr);
+        let prefix = if !prefix_ptr.is_null() {
+            str_from_ptr!(prefix_ptr, prefix_len)
+        } else {
+            ""
+        };
+
+        let suggestions = index
+            .suggest(field_id, prefix, limit)?
+            .into_iter()
+            .map(|suggestion| CBurkazSuggestion {
+                term_ptr: CString::new(suggestion.term)
+                    .map_or(std::ptr::null_mut(), CString::into_raw),
+                doc_freq: suggestion.doc_freq,
+            })
+            .collect::<Vec<_>>();
+
+        unsafe {
+            let boxed_suggestions = suggestions.into_boxed_slice();
+            *suggestion_arr_len_ptr = boxed_suggestions.len();
+            *suggestion_arr_ptr = Box::into_raw(boxed_suggestions).cast();
+        }
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_suggestions(
+    suggestion_arr_ptr: *const CBurkazSuggestion,
+    suggestion_arr_len: usize,
+) {
+    if !suggestion_arr_ptr.is_null() {
+        drop(unsafe {
+            Vec::from_raw_parts(
+                suggestion_arr_ptr.cast_mut(),
+                suggestion_arr_len,
+                suggestion_arr_len,
+            )
+        });
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazSpellingAlternative {
+    pub term_ptr: *mut c_char,
+    pub doc_freq: u64,
+    pub distance: u8,
+}
+
+impl Drop for CBurkazSpellingAlternative {
+    fn drop(&mut self) {
+        if !self.term_ptr.is_null() {
+            drop(unsafe { CString::from_raw(self.term_ptr) });
+        }
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazWordCorrection {
+    /// Byte range of the misspelled word in the query text.
+    pub start: usize,
+    pub end: usize,
+    pub alternative_arr_ptr: *mut CBurkazSpellingAlternative,
+    pub alternative_arr_len: usize,
+}
+
+impl Drop for CBurkazWordCorrection {
+    fn drop(&mut self) {
+        if !self.alternative_arr_ptr.is_null() {
+            drop(unsafe {
+                Vec::from_raw_parts(
+                    self.alternative_arr_ptr,
+                    self.alternative_arr_len,
+                    self.alternative_arr_len,
+                )
+            });
+        }
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazSpellCorrection {
+    /// The query text with every misspelled word replaced by its first
+    /// alternative.
+    pub query_text_ptr: *mut c_char,
+    /// Whether at least one word has an alternative.
+    pub corrected: u8,
+    pub correction_arr_ptr: *mut CBurkazWordCorrection,
+    pub correction_arr_len: usize,
+}
+
+impl From<BurkazSpellCorrection> for CBurkazSpellCorrection {
+    fn from(spell_correction: BurkazSpellCorrection) -> Self {
+        let corrected = spell_correction.is_corrected();
+        let corrections = spell_correction
+            .corrections
+            .into_iter()
+            .map(|correction| {
+                let alternatives = correction
+                    .alternatives
+                    .into_iter()
+                    .map(|alternative| CBurkazSpellingAlternative {
+                        term_ptr: CString::new(alternative.term)
+                            .map_or(std::ptr::null_mut(), CString::into_raw),
+                        doc_freq: alternative.doc_freq,
+                        distance: alternative.distance,
+                    })
+                    .collect::<Vec<_>>();
+                let alternative_arr_len = alternatives.len();
+                let alternative_arr_ptr = if alternatives.is_empty() {
+                    std::ptr::null_mut()
+                } else {
+                    Box::into_raw(alternatives.into_boxed_slice()).cast()
+                };
+                CBurkazWordCorrection {
+                    start: correction.range.start,
+                    end: correction.range.end,
+                    alternative_arr_ptr,
+                    alternative_arr_len,
+                }
+            })
+            .collect::<Vec<_>>();
+        let correction_arr_len = corrections.len();
+        let correction_arr_ptr = if corrections.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            Box::into_raw(corrections.into_boxed_slice()).cast()
+        };
+        Self {
+            query_text_ptr: CString::new(spell_correction.query_text)
+                .map_or(std::ptr::null_mut(), CString::into_raw),
+            corrected: corrected as u8,
+            correction_arr_ptr,
+            correction_arr_len,
+        }
+    }
+}
+
+impl Drop for CBurkazSpellCorrection {
+    fn drop(&mut self) {
+        if !self.query_text_ptr.is_null() {
+            drop(unsafe { CString::from_raw(self.query_text_ptr) });
+        }
+        if !self.correction_arr_ptr.is_null() {
+            drop(unsafe {
+                Vec::from_raw_parts(
+                    self.correction_arr_ptr,
+                    self.correction_arr_len,
+                    self.correction_arr_len,
+                )
+            });
+        }
+    }
+}
+
+/// Offers corrections for the words of a query text found in none of the given
+/// text fields, all indexed text fields when the field array is null.
+///
+/// The result is released with `burkaz_free_spell_correction`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_index_spell_check(
+    index_ptr: *const c_void,
+    query_text_ptr: *const c_char,
+    query_text_len: usize,
+    field_arr_ptr: *const u32,
+    field_arr_len: usize,
+    max_distance: u8,
+    max_alternatives: usize,
+    result_ptr: *mut CBurkazSpellCorrection,
+) -> u8 {
+    catch_error!({
+        let index = index_from_ptr!(index_ptr);
+        let query_text = if !query_text_ptr.is_null() {
+            str_from_ptr!(query_text_ptr, query_text_len)
+        } else {
+            return Err(BurkazError::NullPointer("query text pointer is null"));
+        };
+        let field_ids = slice_from_ptr!(field_arr_ptr, field_arr_len);
+
+        let spell_correction =
+            index.spell_check(query_text, field_ids, max_distance, max_alternatives)?;
+
+        unsafe {
+            result_ptr.write(spell_correction.into());
+        }
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_spell_correction(result: CBurkazSpellCorrection) {
+    drop(result);
+}
+
+fn addrs_from_ptr(addr_arr_ptr: *const u64, addr_arr_len: usize) -> Vec<BurkazObjectAddr> {
+    slice_from_ptr!(addr_arr_ptr, addr_arr_len)
+        .iter()
+        .map(|addr| BurkazObjectAddr::from(*addr))
+        .collect()
+}
+
+fn objects_into_raw(objects: Vec<Option<TantivyDocument>>) -> *const *const c_void {
+    if objects.is_empty() {
+        return std::ptr::null();
+    }
+    let object_ptrs = objects
+        .into_iter()
+        .map(|object| {
+            object.map_or(std::ptr::null(), |object| {
+                Box::into_raw(Box::new(object)).cast_const().cast()
+            })
+        })
+        .collect::<Vec<*const c_void>>();
+    Box::into_raw(object_ptrs.into_boxed_slice()).cast()
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_index_get_by_id(
+    index_ptr: *const c_void,
+    id: u64,
+    object_ptr: *mut *const c_void,
+) -> u8 {
+    catch_error!({
+        let index = index_from_ptr!(index_ptr);
+        let object = index.get_by_id(id)?;
+
+        unsafe {
+            *object_ptr = Box::into_raw(Box::new(object)).cast();
+        }
+
+        ok!()
+    })
+}
+
+/// Pins the current view of the index. The snapshot is released with
+/// `burkaz_free_snapshot`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_index_snapshot(
+    index_ptr: *const c_void,
+    snapshot_ptr_ptr: *mut *const c_void,
+) -> u8 {
+    catch_error!({
+        let index = index_from_ptr!(index_ptr);
+        let snapshot = index.snapshot();
+
+        unsafe {
+            *snapshot_ptr_ptr = snapshot.into_raw().cast();
+        }
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_snapshot(snapshot_ptr: *const c_void) {
+    if !snapshot_ptr.is_null() {
+        drop(unsafe { BurkazSnapshot::from_raw(snapshot_ptr as *mut _) });
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_snapshot_generation(
+    snapshot_ptr: *const c_void,
+    generation_ptr: *mut u64,
+) -> u8 {
+    catch_error!({
+        let snapshot = snapshot_from_ptr!(snapshot_ptr);
+
+        unsafe {
+            *generation_ptr = snapshot.generation();
+        }
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_snapshot_get(
+    snapshot_ptr: *const c_void,
+    addr: u64,
+    object_ptr: *mut *const c_void,
+) -> u8 {
+    catch_error!({
+        let snapshot = snapshot_from_ptr!(snapshot_ptr);
+        let object = snapshot.get(addr.into())?;
+
+        unsafe {
+            *object_ptr = Box::into_raw(Box::new(object)).cast();
+        }
+
+        ok!()
+    })
+}
+
+/// Same as `burkaz_index_get_many`, reading from the snapshot.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_snapshot_get_many(
+    snapshot_ptr: *const c_void,
+    addr_arr_ptr: *const u64,
+    addr_arr_len: usize,
+    object_arr_ptr: *mut *const *const c_void,
+) -> u8 {
+    catch_error!({
+        let snapshot = snapshot_from_ptr!(snapshot_ptr);
+        let objects = snapshot.get_many(&addrs_from_ptr(addr_arr_ptr, addr_arr_len))?;
+
+        unsafe {
+            *object_arr_ptr = objects_into_raw(objects);
+        }
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_snapshot_get_by_id(
+    snapshot_ptr: *const c_void,
+    id: u64,
+    object_ptr: *mut *const c_void,
+) -> u8 {
+    catch_error!({
+        let snapshot = snapshot_from_ptr!(snapshot_ptr);
+        let object = snapshot.get_by_id(id)?;
+
+        unsafe {
+            *object_ptr = Box::into_raw(Box::new(object)).cast();
+        }
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_index_begin_transaction(
+    index_ptr: *const c_void,
+    transaction_ptr_ptr: *mut *const c_void,
+) -> u8 {
+    catch_error!({
+        let index = index_from_ptr!(index_ptr);
+        let transaction = index.begin_transaction();
+
+        unsafe {
+            *transaction_ptr_ptr = transaction.into_raw().cast();
+        }
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_transaction_add(
+    transaction_ptr: *const c_void,
+    object_ptr: *const c_void,
+) -> u8 {
+    catch_error!({
+        if object_ptr.is_null() {
+            return Err(BurkazError::NullPointer("object pointer is null"));
+        }
+        let object = unsafe { *Box::<TantivyDocument>::from_raw(object_ptr as *mut _) };
+
+        let transaction = transaction_from_ptr!(transaction_ptr);
+        transaction.add(object);
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_transaction_add_all(
+    transaction_ptr: *const c_void,
+    object_array_ptr: *const *const c_void,
+    object_array_len: usize,
+) -> u8 {
+    catch_error!({
+        let objects = unsafe {
+            if object_array_ptr.is_null() {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(object_array_ptr, object_array_len)
+                    .iter()
+                    .map(|object_ptr| *Box::<TantivyDocument>::from_raw(*object_ptr as *mut _))
+                    .collect()
+            }
+        };
+
+        let transaction = transaction_from_ptr!(transaction_ptr);
+        transaction.add_all(objects);
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_transaction_delete_all_by_query(
+    transaction_ptr: *const c_void,
+    query_ptr: *const c_void,
+) -> u8 {
+    catch_error!({
+        if query_ptr.is_null() {
+            return Err(BurkazError::NullPointer("query pointer is null"));
+        }
+        let query = query_from_ptr!(query_ptr);
+
+        let transaction = transaction_from_ptr!(transaction_ptr);
+        transaction.delete_all_by_query(query);
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_transaction_upsert(
+    transaction_ptr: *const c_void,
+    object_ptr: *const c_void,
+) -> u8 {
+    catch_error!({
+        if object_ptr.is_null() {
+            return Err(BurkazError::NullPointer("object pointer is null"));
+        }
+        let object = unsafe { *Box::<TantivyDocument>::from_raw(object_ptr as *mut _) };
+
+        let transaction = transaction_from_ptr!(transaction_ptr);
+        transaction.upsert(object);
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_transaction_delete_by_key(
+    transaction_ptr: *const c_void,
+    term_ptr: *const c_void,
+) -> u8 {
+    catch_error!({
+        if term_ptr.is_null() {
+            return Err(BurkazError::NullPointer("term pointer is null"));
+        }
+        let key = term_from_ptr!(term_ptr);
+
+        let transaction = transaction_from_ptr!(transaction_ptr);
+        transaction.delete_by_key(key);
+
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_transaction_clear(transaction_ptr: *const c_void) -> u8 {
+    catch_error!({
+        let transaction = transaction_from_ptr!(transaction_ptr);
+        transaction.clear();
+
+        ok!()
+    })
+}
+
+/// Commits and frees the transaction. The transaction pointer must not be used
+/// afterwards, even if the commit fails.
+///
+/// Writes the stable ids given to the added and upserted objects, in order, to
+/// `id_arr_ptr` unless it is null. They are released with
+/// `burkaz_free_transaction_ids`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_transaction_commit(
+    transaction_ptr: *const c_void,
+    id_arr_ptr: *mut *const u64,
+    id_arr_len_ptr: *mut usize,
+) -> u8 {
+    catch_error!({
+        if transaction_ptr.is_null() {
+            return Err(BurkazError::NullPointer("transaction pointer is null"));
+        }
+        let transaction = unsafe { BurkazTransaction::from_raw(transaction_ptr as *mut _) };
+        let ids = transaction.commit()?;
+        if id_arr_ptr.is_null() {
+            return ok!();
+        }
+        unsafe {
+            if ids.is_empty() {
+                *id_arr_ptr = std::ptr::null();
+                *id_arr_len_ptr = 0;
+            } else {
+                let boxed_ids = ids.into_boxed_slice();
+                *id_arr_len_ptr = boxed_ids.len();
+                *id_arr_ptr = Box::into_raw(boxed_ids).cast();
+            }
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_transaction_ids(id_arr_ptr: *const u64, id_arr_len: usize) {
+    if !id_arr_ptr.is_null() {
+        drop(unsafe { Vec::from_raw_parts(id_arr_ptr.cast_mut(), id_arr_len, id_arr_len) });
+    }
+}
+
+/// Discards and frees the transaction.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_transaction_rollback(transaction_ptr: *const c_void) {
+    if !transaction_ptr.is_null() {
+        let transaction = unsafe { BurkazTransaction::from_raw(transaction_ptr as *mut _) };
+        transaction.rollback();
+    }
+}
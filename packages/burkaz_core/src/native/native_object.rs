@@ -1,6 +1,11 @@
 use std::ffi::{CStr, CString, c_char, c_void};
 
-use tantivy::{TantivyDocument, schema::Value};
+use tantivy::{
+    DateTime, TantivyDocument,
+    schema::{Facet, OwnedValue, Value},
+};
+
+use crate::str_from_ptr;
 
 macro_rules! tantivy_doc_from_ptr {
     ($ptr:expr) => {
@@ -233,3 +238,406 @@ pub extern "C" fn burkaz_object_write_text_list(
         object.add_text(field, value);
     }
 }
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_f64(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_ptr: *mut f64,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let mut values = object.get_all(tantivy::schema::Field::from_field_id(field_id));
+    let value_opt = values.next().and_then(|value| value.as_f64());
+    if let Some(value) = value_opt {
+        unsafe { *value_ptr = value };
+        true
+    } else {
+        false
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_f64_list(
+    object_ptr: *const c_void,
+    field_id: u32,
+    result_arr_ptr: *mut *const f64,
+    result_arr_len_ptr: *mut usize,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let values = object
+        .get_all(tantivy::schema::Field::from_field_id(field_id))
+        .filter_map(|value| value.as_f64())
+        .collect::<Vec<_>>();
+    let len = values.len();
+    unsafe {
+        let values_boxed_slice = values.into_boxed_slice();
+        *result_arr_ptr = Box::into_raw(values_boxed_slice).cast();
+        *result_arr_len_ptr = len;
+    }
+    len != 0
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_write_f64(object_ptr: *const c_void, field_id: u32, value: f64) {
+    let object = tantivy_doc_from_ptr_mut!(object_ptr);
+    object.add_f64(tantivy::schema::Field::from_field_id(field_id), value);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_write_f64_list(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_arr_ptr: *const f64,
+    value_arr_len: usize,
+) {
+    let object = tantivy_doc_from_ptr_mut!(object_ptr);
+    let field = tantivy::schema::Field::from_field_id(field_id);
+    let values = unsafe { std::slice::from_raw_parts(value_arr_ptr, value_arr_len) };
+    for value in values {
+        object.add_f64(field, *value);
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_u64(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_ptr: *mut u64,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let mut values = object.get_all(tantivy::schema::Field::from_field_id(field_id));
+    let value_opt = values.next().and_then(|value| value.as_u64());
+    if let Some(value) = value_opt {
+        unsafe { *value_ptr = value };
+        true
+    } else {
+        false
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_u64_list(
+    object_ptr: *const c_void,
+    field_id: u32,
+    result_arr_ptr: *mut *const u64,
+    result_arr_len_ptr: *mut usize,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let values = object
+        .get_all(tantivy::schema::Field::from_field_id(field_id))
+        .filter_map(|value| value.as_u64())
+        .collect::<Vec<_>>();
+    let len = values.len();
+    unsafe {
+        let values_boxed_slice = values.into_boxed_slice();
+        *result_arr_ptr = Box::into_raw(values_boxed_slice).cast();
+        *result_arr_len_ptr = len;
+    }
+    len != 0
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_write_u64(object_ptr: *const c_void, field_id: u32, value: u64) {
+    let object = tantivy_doc_from_ptr_mut!(object_ptr);
+    object.add_u64(tantivy::schema::Field::from_field_id(field_id), value);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_write_u64_list(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_arr_ptr: *const u64,
+    value_arr_len: usize,
+) {
+    let object = tantivy_doc_from_ptr_mut!(object_ptr);
+    let field = tantivy::schema::Field::from_field_id(field_id);
+    let values = unsafe { std::slice::from_raw_parts(value_arr_ptr, value_arr_len) };
+    for value in values {
+        object.add_u64(field, *value);
+    }
+}
+
+/// Dates cross the FFI boundary as microseconds since the Unix epoch.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_date(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_ptr: *mut i64,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let mut values = object.get_all(tantivy::schema::Field::from_field_id(field_id));
+    let value_opt = values
+        .next()
+        .and_then(|value| value.as_datetime())
+        .map(|date| date.into_timestamp_micros());
+    if let Some(value) = value_opt {
+        unsafe { *value_ptr = value };
+        true
+    } else {
+        false
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_date_list(
+    object_ptr: *const c_void,
+    field_id: u32,
+    result_arr_ptr: *mut *const i64,
+    result_arr_len_ptr: *mut usize,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let values = object
+        .get_all(tantivy::schema::Field::from_field_id(field_id))
+        .filter_map(|value| value.as_datetime())
+        .map(|date| date.into_timestamp_micros())
+        .collect::<Vec<_>>();
+    let len = values.len();
+    unsafe {
+        let values_boxed_slice = values.into_boxed_slice();
+        *result_arr_ptr = Box::into_raw(values_boxed_slice).cast();
+        *result_arr_len_ptr = len;
+    }
+    len != 0
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_write_date(object_ptr: *const c_void, field_id: u32, value: i64) {
+    let object = tantivy_doc_from_ptr_mut!(object_ptr);
+    object.add_date(
+        tantivy::schema::Field::from_field_id(field_id),
+        DateTime::from_timestamp_micros(value),
+    );
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_write_date_list(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_arr_ptr: *const i64,
+    value_arr_len: usize,
+) {
+    let object = tantivy_doc_from_ptr_mut!(object_ptr);
+    let field = tantivy::schema::Field::from_field_id(field_id);
+    let values = unsafe { std::slice::from_raw_parts(value_arr_ptr, value_arr_len) };
+    for value in values {
+        object.add_date(field, DateTime::from_timestamp_micros(*value));
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazBytes {
+    pub ptr: *mut u8,
+    pub len: usize,
+}
+
+impl From<&[u8]> for CBurkazBytes {
+    fn from(bytes: &[u8]) -> Self {
+        let len = bytes.len();
+        let ptr = Box::into_raw(bytes.to_vec().into_boxed_slice()).cast();
+        Self { ptr, len }
+    }
+}
+
+/// The caller is responsible for freeing the bytes via `burkaz_object_free_bytes`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_bytes(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_ptr: *mut CBurkazBytes,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let mut values = object.get_all(tantivy::schema::Field::from_field_id(field_id));
+    let value_opt = values.next().and_then(|value| value.as_bytes());
+    if let Some(value) = value_opt {
+        unsafe { *value_ptr = value.into() };
+        true
+    } else {
+        false
+    }
+}
+
+/// The caller is responsible for freeing the list via `burkaz_object_free_bytes_list`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_bytes_list(
+    object_ptr: *const c_void,
+    field_id: u32,
+    result_arr_ptr: *mut *const CBurkazBytes,
+    result_arr_len_ptr: *mut usize,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let values = object
+        .get_all(tantivy::schema::Field::from_field_id(field_id))
+        .filter_map(|value| value.as_bytes().map(CBurkazBytes::from))
+        .collect::<Vec<_>>();
+    let len = values.len();
+    unsafe {
+        let values_boxed_slice = values.into_boxed_slice();
+        *result_arr_ptr = Box::into_raw(values_boxed_slice).cast();
+        *result_arr_len_ptr = len;
+    }
+    len != 0
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_free_bytes(bytes: CBurkazBytes) {
+    if !bytes.ptr.is_null() {
+        drop(unsafe { Vec::from_raw_parts(bytes.ptr, bytes.len, bytes.len) });
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_free_bytes_list(
+    result_arr_ptr: *mut CBurkazBytes,
+    result_arr_len: usize,
+) {
+    if !result_arr_ptr.is_null() {
+        let values = unsafe { Vec::from_raw_parts(result_arr_ptr, result_arr_len, result_arr_len) };
+        for value in values {
+            burkaz_object_free_bytes(value);
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_write_bytes(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_ptr: *const u8,
+    value_len: usize,
+) {
+    let object = tantivy_doc_from_ptr_mut!(object_ptr);
+    let value = unsafe { std::slice::from_raw_parts(value_ptr, value_len) };
+    object.add_bytes(tantivy::schema::Field::from_field_id(field_id), value);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_write_bytes_list(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_arr_ptr: *const *const u8,
+    value_len_arr_ptr: *const usize,
+    value_arr_len: usize,
+) {
+    let object = tantivy_doc_from_ptr_mut!(object_ptr);
+    let field = tantivy::schema::Field::from_field_id(field_id);
+    let values = unsafe { std::slice::from_raw_parts(value_arr_ptr, value_arr_len) };
+    let value_lens = unsafe { std::slice::from_raw_parts(value_len_arr_ptr, value_arr_len) };
+    for (value, value_len) in values.iter().zip(value_lens) {
+        let value = unsafe { std::slice::from_raw_parts(*value, *value_len) };
+        object.add_bytes(field, value);
+    }
+}
+
+/// Writes a JSON object, given as UTF-8 encoded JSON text, to a JSON field.
+///
+/// Returns `false` when the text is not a valid JSON object.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_write_json(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_ptr: *const c_char,
+    value_len: usize,
+) -> bool {
+    let object = tantivy_doc_from_ptr_mut!(object_ptr);
+    let value = str_from_ptr!(value_ptr, value_len);
+    let Ok(value) = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(value)
+    else {
+        return false;
+    };
+    object.add_field_value(
+        tantivy::schema::Field::from_field_id(field_id),
+        &OwnedValue::from(value),
+    );
+    true
+}
+
+/// Reads the first JSON object of a field as JSON text.
+///
+/// The caller is responsible for freeing the string via `burkaz_free_string`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_json(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_ptr: *mut *const c_char,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let mut values = object.get_all(tantivy::schema::Field::from_field_id(field_id));
+    let value_opt = values
+        .next()
+        .filter(|value| value.as_object().is_some())
+        .and_then(|value| serde_json::to_string(&OwnedValue::from(value)).ok())
+        .and_then(|value| CString::new(value).ok());
+    if let Some(value) = value_opt {
+        unsafe { *value_ptr = value.into_raw() };
+        true
+    } else {
+        false
+    }
+}
+
+/// Writes a facet given as a path, e.g. `/electronics/phones`.
+///
+/// Returns `false` when the path is not a valid facet.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_write_facet(
+    object_ptr: *const c_void,
+    field_id: u32,
+    path_ptr: *const c_char,
+    path_len: usize,
+) -> bool {
+    let object = tantivy_doc_from_ptr_mut!(object_ptr);
+    let Ok(facet) = Facet::from_text(str_from_ptr!(path_ptr, path_len)) else {
+        return false;
+    };
+    object.add_facet(tantivy::schema::Field::from_field_id(field_id), facet);
+    true
+}
+
+/// The caller is responsible for freeing the path via `burkaz_free_string`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_facet(
+    object_ptr: *const c_void,
+    field_id: u32,
+    value_ptr: *mut *const c_char,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let mut values = object.get_all(tantivy::schema::Field::from_field_id(field_id));
+    let value_opt = values
+        .next()
+        .and_then(|value| value.as_facet())
+        .and_then(facet_path);
+    if let Some(value) = value_opt {
+        unsafe { *value_ptr = value.into_raw() };
+        true
+    } else {
+        false
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_object_read_facet_list(
+    object_ptr: *const c_void,
+    field_id: u32,
+    result_arr_ptr: *mut *const c_char,
+    result_arr_len_ptr: *mut usize,
+) -> bool {
+    let object = tantivy_doc_from_ptr!(object_ptr);
+    let values = object
+        .get_all(tantivy::schema::Field::from_field_id(field_id))
+        .filter_map(|value| value.as_facet())
+        .filter_map(facet_path)
+        .map(CString::into_raw)
+        .collect::<Vec<_>>();
+    let len = values.len();
+    unsafe {
+        let values_boxed_slice = values.into_boxed_slice();
+        *result_arr_ptr = Box::into_raw(values_boxed_slice).cast();
+        *result_arr_len_ptr = len;
+    }
+    len != 0
+}
+
+fn facet_path(encoded: &str) -> Option<CString> {
+    let facet = Facet::from_encoded(encoded.as_bytes().to_vec()).ok()?;
+    CString::new(facet.to_string()).ok()
+}
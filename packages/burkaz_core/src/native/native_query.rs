@@ -1,6 +1,17 @@
-use std::ffi::{CStr, c_char, c_void};
+use std::{
+    ffi::{CStr, c_char, c_void},
+    ops::Bound,
+};
 
-use crate::{query::BurkazQuery, schema::BurkazIndexingStrategy, term_from_ptr};
+use crate::{
+    error::BurkazError,
+    native::native_query_parser::{CBurkazQueryParserOptions, query_parser_options_from_native},
+    query::BurkazQuery,
+    schema::BurkazIndexingStrategy,
+    slice_from_ptr, str_from_ptr,
+    term::BurkazTerm,
+    term_from_ptr,
+};
 
 #[macro_export]
 macro_rules! query_from_ptr {
@@ -62,6 +73,32 @@ pub extern "C" fn burkaz_not_query(query_ptr: *const c_void) -> *const c_void {
     query_into_raw!(query)
 }
 
+/// Null arrays are empty, a negative `minimum_should_match` keeps the default.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_boolean_query(
+    must_arr_ptr: *const *const c_void,
+    must_arr_len: usize,
+    should_arr_ptr: *const *const c_void,
+    should_arr_len: usize,
+    must_not_arr_ptr: *const *const c_void,
+    must_not_arr_len: usize,
+    minimum_should_match: isize,
+) -> *const c_void {
+    let queries = |arr_ptr: *const *const c_void, arr_len: usize| {
+        slice_from_ptr!(arr_ptr, arr_len)
+            .iter()
+            .map(|query| query_from_ptr!(*query))
+            .collect()
+    };
+    let query = BurkazQuery::Boolean {
+        must: queries(must_arr_ptr, must_arr_len),
+        should: queries(should_arr_ptr, should_arr_len),
+        must_not: queries(must_not_arr_ptr, must_not_arr_len),
+        minimum_should_match: usize::try_from(minimum_should_match).ok(),
+    };
+    query_into_raw!(query)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn burkaz_boost_query(query_ptr: *const c_void, boost: f32) -> *const c_void {
     let query = unsafe { Box::from_raw(query_ptr as *mut BurkazQuery) };
@@ -120,6 +157,56 @@ pub extern "C" fn burkaz_term_set_query(
     query_into_raw!(query)
 }
 
+/// Bound kinds: `0` unbounded, `1` inclusive, `2` exclusive.
+///
+/// A null term pointer is treated as unbounded. Fails for unknown bound kinds
+/// and for bounds on different fields, the terms are released either way.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_range_query(
+    lower_term_ptr: *const c_void,
+    lower_bound_kind: u8,
+    upper_term_ptr: *const c_void,
+    upper_bound_kind: u8,
+    query_ptr_ptr: *mut *const c_void,
+) -> u8 {
+    catch_error!({
+        let lower = bound_from_native(lower_term_ptr, lower_bound_kind);
+        let upper = bound_from_native(upper_term_ptr, upper_bound_kind);
+        let (lower, upper) = (lower?, upper?);
+        if let (
+            Bound::Included(lower) | Bound::Excluded(lower),
+            Bound::Included(upper) | Bound::Excluded(upper),
+        ) = (&lower, &upper)
+            && lower.field_id() != upper.field_id()
+        {
+            return Err(BurkazError::InvalidQuery(
+                "range bounds target different fields".to_owned(),
+            ));
+        }
+
+        let query = BurkazQuery::Range { lower, upper };
+        unsafe {
+            *query_ptr_ptr = query_into_raw!(query);
+        }
+        ok!()
+    })
+}
+
+fn bound_from_native(term_ptr: *const c_void, bound_kind: u8) -> crate::Result<Bound<BurkazTerm>> {
+    if term_ptr.is_null() {
+        return Ok(Bound::Unbounded);
+    }
+    let term = term_from_ptr!(term_ptr);
+    match bound_kind {
+        0 => Ok(Bound::Unbounded),
+        1 => Ok(Bound::Included(term)),
+        2 => Ok(Bound::Excluded(term)),
+        _ => Err(BurkazError::InvalidQuery(format!(
+            "unknown bound kind {bound_kind}"
+        ))),
+    }
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn burkaz_phrase_query(
     term_arr_ptr: *const *const c_void,
@@ -160,8 +247,68 @@ pub extern "C" fn burkaz_parse_query(
     query_text_len: usize,
 ) -> *const c_void {
     let query_text = unsafe {
-        std::str::from_utf8_unchecked(std::slice::from_raw_parts(query_text_ptr.cast(), query_text_len))
+        std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+            query_text_ptr.cast(),
+            query_text_len,
+        ))
+    };
+    let query = BurkazQuery::Parse {
+        query_text: query_text.to_owned(),
+        lenient: false,
+        options: None,
+    };
+    query_into_raw!(query)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_autocomplete_query(
+    field_id: u32,
+    text_ptr: *const c_char,
+    text_len: usize,
+) -> *const c_void {
+    if text_ptr.is_null() {
+        return burkaz_empty_query();
+    }
+    let text = str_from_ptr!(text_ptr, text_len);
+    let query = BurkazQuery::Autocomplete {
+        field_id,
+        text: text.to_owned(),
+    };
+    query_into_raw!(query)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_parse_query_lenient(
+    query_text_ptr: *const c_char,
+    query_text_len: usize,
+) -> *const c_void {
+    let query_text = str_from_ptr!(query_text_ptr, query_text_len);
+    let query = BurkazQuery::Parse {
+        query_text: query_text.to_owned(),
+        lenient: true,
+        options: None,
+    };
+    query_into_raw!(query)
+}
+
+/// Parses the query with its own query parser instead of the index one.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_parse_query_with_options(
+    query_text_ptr: *const c_char,
+    query_text_len: usize,
+    lenient: bool,
+    options_ptr: *const CBurkazQueryParserOptions,
+) -> *const c_void {
+    let query_text = str_from_ptr!(query_text_ptr, query_text_len);
+    let options = if options_ptr.is_null() {
+        None
+    } else {
+        Some(query_parser_options_from_native(unsafe { &*options_ptr }))
+    };
+    let query = BurkazQuery::Parse {
+        query_text: query_text.to_owned(),
+        lenient,
+        options,
     };
-    let query = BurkazQuery::Parse { query_text: query_text.to_owned() };
     query_into_raw!(query)
 }
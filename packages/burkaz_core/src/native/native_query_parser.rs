@@ -0,0 +1,67 @@
+use crate::{
+    query_parser::{BurkazFuzziness, BurkazQueryParserOptions},
+    slice_from_ptr,
+};
+
+#[repr(C)]
+pub struct CBurkazFieldBoost {
+    pub field_id: u32,
+    pub boost: f32,
+}
+
+#[repr(C)]
+pub struct CBurkazFieldFuzziness {
+    pub field_id: u32,
+    pub enabled: u8,
+    pub prefix: u8,
+    pub distance: u8,
+    pub transposition_cost_one: u8,
+}
+
+#[repr(C)]
+pub struct CBurkazQueryParserOptions {
+    pub default_field_arr_ptr: *const u32,
+    pub default_field_arr_len: usize,
+    pub conjunction_by_default: u8,
+    pub field_boost_arr_ptr: *const CBurkazFieldBoost,
+    pub field_boost_arr_len: usize,
+    pub field_fuzziness_arr_ptr: *const CBurkazFieldFuzziness,
+    pub field_fuzziness_arr_len: usize,
+}
+
+pub fn query_parser_options_from_native(
+    c_options: &CBurkazQueryParserOptions,
+) -> BurkazQueryParserOptions {
+    let default_fields = slice_from_ptr!(
+        c_options.default_field_arr_ptr,
+        c_options.default_field_arr_len
+    );
+
+    let field_boosts =
+        slice_from_ptr!(c_options.field_boost_arr_ptr, c_options.field_boost_arr_len)
+            .iter()
+            .map(|c_boost| (c_boost.field_id, c_boost.boost))
+            .collect();
+
+    let field_fuzziness = slice_from_ptr!(
+        c_options.field_fuzziness_arr_ptr,
+        c_options.field_fuzziness_arr_len
+    )
+    .iter()
+    .map(|c_fuzziness| {
+        let fuzziness = (c_fuzziness.enabled != 0).then_some(BurkazFuzziness {
+            prefix: c_fuzziness.prefix != 0,
+            distance: c_fuzziness.distance,
+            transposition_cost_one: c_fuzziness.transposition_cost_one != 0,
+        });
+        (c_fuzziness.field_id, fuzziness)
+    })
+    .collect();
+
+    BurkazQueryParserOptions {
+        default_fields: default_fields.to_vec(),
+        conjunction_by_default: c_options.conjunction_by_default != 0,
+        field_boosts,
+        field_fuzziness,
+    }
+}
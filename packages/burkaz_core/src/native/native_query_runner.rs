@@ -1,6 +1,18 @@
-use std::ffi::c_void;
+use std::ffi::{CString, c_char, c_void};
 
-use crate::{address::BurkazObjectAddr, query::BurkazQuery, query_runner::QueryRunner};
+use crate::{
+    address::{BurkazObjectAddr, BurkazScoredObjectAddr},
+    aggregation::{BurkazMetricsAggregation, BurkazTermsAggregation},
+    cursor::BurkazSearchCursor,
+    error::BurkazError,
+    native::native_aggregation::{
+        CBurkazHistogramAggregation, CBurkazHistogramAggregationResult,
+        CBurkazMetricsAggregationResult, CBurkazTermsBucket, histogram_aggregation_from_native,
+    },
+    query::BurkazQuery,
+    query_runner::{BurkazSortOrder, QueryRunner},
+    slice_from_ptr, str_from_ptr,
+};
 
 macro_rules! query_runner_from_ptr {
     ($query_runner_ptr:expr) => {
@@ -33,10 +45,39 @@ pub extern "C" fn burkaz_query_runner_new(
     })
 }
 
+/// Creates a query runner reading from the snapshot instead of the latest view
+/// of the index. The snapshot is copied, so it may be released right after.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_new_with_snapshot(
+    index_ptr: *const c_void,
+    query_ptr: *const c_void,
+    snapshot_ptr: *const c_void,
+    query_runner_ptr: *mut *const c_void,
+) -> u8 {
+    catch_error!({
+        let index = index_from_ptr!(index_ptr);
+        let snapshot = snapshot_from_ptr!(snapshot_ptr).clone();
+
+        let query = if query_ptr.is_null() {
+            BurkazQuery::Empty
+        } else {
+            query_from_ptr!(query_ptr)
+        };
+
+        let query_runner = QueryRunner::new(index.downgrade(), query).with_snapshot(snapshot);
+
+        unsafe {
+            *query_runner_ptr = query_runner.into_raw().cast();
+        }
+
+        ok!()
+    })
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn burkaz_free_query_runner(query_runner_ptr: *const c_void) {
     if !query_runner_ptr.is_null() {
-        drop(unsafe { Box::from_raw(query_runner_ptr.cast_mut()) });
+        drop(unsafe { Box::from_raw(query_runner_ptr as *mut QueryRunner) });
     }
 }
 
@@ -94,6 +135,570 @@ pub extern "C" fn burkaz_free_query_runner_search_result(
     }
 }
 
+#[repr(C)]
+pub struct CBurkazHit {
+    pub addr: BurkazScoredObjectAddr,
+    pub object_ptr: *const c_void,
+}
+
+/// Runs the query and reads the matching objects in the same pass, keeping only
+/// the given fields. A null field array keeps every stored field.
+///
+/// The objects are owned by the caller and released with `burkaz_free_object`,
+/// the hits with `burkaz_free_query_runner_hits`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_search_objects(
+    query_runner_ptr: *const c_void,
+    offset: usize,
+    limit: usize,
+    field_id_arr_ptr: *const u32,
+    field_id_arr_len: usize,
+    result_arr_ptr: *mut *const CBurkazHit,
+    result_arr_len_ptr: *mut usize,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        let field_ids = slice_from_ptr!(field_id_arr_ptr, field_id_arr_len);
+        let hits = query_runner.search_objects(offset, limit, field_ids)?;
+        if hits.is_empty() {
+            return ok!();
+        }
+        let hits = hits
+            .into_iter()
+            .map(|hit| CBurkazHit {
+                addr: hit.addr,
+                object_ptr: Box::into_raw(Box::new(hit.object)).cast_const().cast(),
+            })
+            .collect::<Vec<_>>();
+        unsafe {
+            let boxed_hits = hits.into_boxed_slice();
+            *result_arr_len_ptr = boxed_hits.len();
+            *result_arr_ptr = Box::into_raw(boxed_hits).cast();
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_query_runner_hits(
+    result_arr_ptr: *const CBurkazHit,
+    result_arr_len: usize,
+) {
+    if !result_arr_ptr.is_null() {
+        drop(unsafe {
+            Vec::from_raw_parts(result_arr_ptr.cast_mut(), result_arr_len, result_arr_len)
+        });
+    }
+}
+
+/// Returns the stable ids of the matching objects, most relevant first.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_search_ids(
+    query_runner_ptr: *const c_void,
+    offset: usize,
+    limit: usize,
+    result_arr_ptr: *mut *const u64,
+    result_arr_len_ptr: *mut usize,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        let ids = query_runner.search_ids(offset, limit)?;
+        if ids.is_empty() {
+            return ok!();
+        }
+        unsafe {
+            let boxed_ids = ids.into_boxed_slice();
+            *result_arr_len_ptr = boxed_ids.len();
+            *result_arr_ptr = Box::into_raw(boxed_ids).cast();
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_query_runner_search_ids(
+    result_arr_ptr: *const u64,
+    result_arr_len: usize,
+) {
+    if !result_arr_ptr.is_null() {
+        drop(unsafe {
+            Vec::from_raw_parts(result_arr_ptr.cast_mut(), result_arr_len, result_arr_len)
+        });
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_search_scored(
+    query_runner_ptr: *const c_void,
+    offset: usize,
+    limit: usize,
+    result_arr_ptr: *mut *const BurkazScoredObjectAddr,
+    result_arr_len_ptr: *mut usize,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        let objects = query_runner.search_scored(offset, limit)?;
+        if objects.is_empty() {
+            return ok!();
+        }
+        unsafe {
+            let boxed_objects = objects.into_boxed_slice();
+            *result_arr_len_ptr = boxed_objects.len();
+            *result_arr_ptr = Box::into_raw(boxed_objects).cast();
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_query_runner_search_scored_result(
+    result_arr_ptr: *const BurkazScoredObjectAddr,
+    result_arr_len: usize,
+) {
+    if !result_arr_ptr.is_null() {
+        drop(unsafe {
+            Vec::from_raw_parts(result_arr_ptr.cast_mut(), result_arr_len, result_arr_len)
+        });
+    }
+}
+
+/// Sort orders: `1` ascending, `2` descending, other codes are rejected.
+///
+/// The result is released with `burkaz_free_query_runner_search_result`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_search_sorted(
+    query_runner_ptr: *const c_void,
+    field_id: u32,
+    order: u8,
+    offset: usize,
+    limit: usize,
+    result_arr_ptr: *mut *const u64,
+    result_arr_len_ptr: *mut usize,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        let order = BurkazSortOrder::from_code(order)
+            .ok_or_else(|| BurkazError::InvalidQuery(format!("unknown sort order {}", order)))?;
+        let objects = query_runner.search_sorted(field_id, order, offset, limit)?;
+        if objects.is_empty() {
+            return ok!();
+        }
+        unsafe {
+            let boxed_objects = objects.into_boxed_slice();
+            *result_arr_len_ptr = boxed_objects.len();
+            *result_arr_ptr = Box::into_raw(boxed_objects).cast();
+        }
+        ok!()
+    })
+}
+
+/// Sort orders: `0` by relevance, `1` ascending and `2` descending by the field,
+/// other codes are rejected. A null cursor returns the first page.
+///
+/// The objects are released with `burkaz_free_query_runner_search_scored_result`
+/// and the next cursor with `burkaz_free_search_cursor`. No cursor is returned
+/// when the page holds fewer than `limit` objects, it is the last one.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_search_after(
+    query_runner_ptr: *const c_void,
+    field_id: u32,
+    order: u8,
+    cursor_ptr: *const u8,
+    cursor_len: usize,
+    limit: usize,
+    result_arr_ptr: *mut *const BurkazScoredObjectAddr,
+    result_arr_len_ptr: *mut usize,
+    next_cursor_ptr: *mut *const u8,
+    next_cursor_len_ptr: *mut usize,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        let sort = match order {
+            0 => None,
+            _ => Some((
+                field_id,
+                BurkazSortOrder::from_code(order).ok_or_else(|| {
+                    BurkazError::InvalidQuery(format!("unknown sort order {}", order))
+                })?,
+            )),
+        };
+        let cursor = if cursor_ptr.is_null() {
+            None
+        } else {
+            let cursor_bytes = unsafe { std::slice::from_raw_parts(cursor_ptr, cursor_len) };
+            Some(BurkazSearchCursor::from_bytes(cursor_bytes)?)
+        };
+        let page = query_runner.search_after(sort, cursor.as_ref(), limit)?;
+        if let Some(cursor) = page.cursor {
+            unsafe {
+                let boxed_cursor = cursor.to_bytes().into_boxed_slice();
+                *next_cursor_len_ptr = boxed_cursor.len();
+                *next_cursor_ptr = Box::into_raw(boxed_cursor).cast();
+            }
+        }
+        if page.addrs.is_empty() {
+            return ok!();
+        }
+        unsafe {
+            let boxed_objects = page.addrs.into_boxed_slice();
+            *result_arr_len_ptr = boxed_objects.len();
+            *result_arr_ptr = Box::into_raw(boxed_objects).cast();
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_search_cursor(cursor_ptr: *const u8, cursor_len: usize) {
+    if !cursor_ptr.is_null() {
+        drop(unsafe { Vec::from_raw_parts(cursor_ptr.cast_mut(), cursor_len, cursor_len) });
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazQueryParseError {
+    /// Byte offset of the error in the query string, or `-1` when unknown.
+    pub position: i64,
+    pub message_ptr: *mut c_char,
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_parse_errors(
+    query_runner_ptr: *const c_void,
+    result_arr_ptr: *mut *const CBurkazQueryParseError,
+    result_arr_len_ptr: *mut usize,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        let errors = query_runner.parse_errors()?;
+        if errors.is_empty() {
+            return ok!();
+        }
+        let errors = errors
+            .into_iter()
+            .map(|error| CBurkazQueryParseError {
+                position: error.position.map_or(-1, |position| position as i64),
+                message_ptr: CString::new(error.message)
+                    .map_or(std::ptr::null_mut(), CString::into_raw),
+            })
+            .collect::<Vec<_>>();
+        unsafe {
+            let boxed_errors = errors.into_boxed_slice();
+            *result_arr_len_ptr = boxed_errors.len();
+            *result_arr_ptr = Box::into_raw(boxed_errors).cast();
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_query_runner_parse_errors(
+    result_arr_ptr: *const CBurkazQueryParseError,
+    result_arr_len: usize,
+) {
+    if !result_arr_ptr.is_null() {
+        let errors = unsafe {
+            Vec::from_raw_parts(result_arr_ptr.cast_mut(), result_arr_len, result_arr_len)
+        };
+        for error in errors {
+            if !error.message_ptr.is_null() {
+                drop(unsafe { CString::from_raw(error.message_ptr) });
+            }
+        }
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazFacetCount {
+    pub facet_ptr: *mut c_char,
+    pub count: u64,
+}
+
+/// Counts the matching objects under each direct child of the given root
+/// facets, keeping the `top_k` most frequent children per root (`0` keeps all).
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_facet_counts(
+    query_runner_ptr: *const c_void,
+    field_id: u32,
+    root_path_arr_ptr: *const *const c_char,
+    root_path_len_arr_ptr: *const usize,
+    root_path_arr_len: usize,
+    top_k: usize,
+    result_arr_ptr: *mut *const CBurkazFacetCount,
+    result_arr_len_ptr: *mut usize,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        let root_paths = if root_path_arr_ptr.is_null() {
+            vec!["/"]
+        } else {
+            let root_path_ptrs =
+                unsafe { std::slice::from_raw_parts(root_path_arr_ptr, root_path_arr_len) };
+            let root_path_lens =
+                unsafe { std::slice::from_raw_parts(root_path_len_arr_ptr, root_path_arr_len) };
+            root_path_ptrs
+                .iter()
+                .zip(root_path_lens)
+                .map(|(path_ptr, path_len)| str_from_ptr!(*path_ptr, *path_len))
+                .collect()
+        };
+        let counts = query_runner.facet_counts(field_id, &root_paths, top_k)?;
+        if counts.is_empty() {
+            return ok!();
+        }
+        let counts = counts
+            .into_iter()
+            .map(|count| CBurkazFacetCount {
+                facet_ptr: CString::new(count.facet)
+                    .map_or(std::ptr::null_mut(), CString::into_raw),
+                count: count.count,
+            })
+            .collect::<Vec<_>>();
+        unsafe {
+            let boxed_counts = counts.into_boxed_slice();
+            *result_arr_len_ptr = boxed_counts.len();
+            *result_arr_ptr = Box::into_raw(boxed_counts).cast();
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_query_runner_facet_counts(
+    result_arr_ptr: *const CBurkazFacetCount,
+    result_arr_len: usize,
+) {
+    if !result_arr_ptr.is_null() {
+        let counts = unsafe {
+            Vec::from_raw_parts(result_arr_ptr.cast_mut(), result_arr_len, result_arr_len)
+        };
+        for count in counts {
+            if !count.facet_ptr.is_null() {
+                drop(unsafe { CString::from_raw(count.facet_ptr) });
+            }
+        }
+    }
+}
+
+#[repr(C)]
+pub struct CBurkazHighlightRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[repr(C)]
+pub struct CBurkazSnippet {
+    pub fragment_ptr: *mut c_char,
+    /// Byte ranges of the fragment matching the query.
+    pub highlighted_arr_ptr: *mut CBurkazHighlightRange,
+    pub highlighted_arr_len: usize,
+}
+
+impl Drop for CBurkazSnippet {
+    fn drop(&mut self) {
+        if !self.fragment_ptr.is_null() {
+            drop(unsafe { CString::from_raw(self.fragment_ptr) });
+        }
+        if !self.highlighted_arr_ptr.is_null() {
+            drop(unsafe {
+                Vec::from_raw_parts(
+                    self.highlighted_arr_ptr,
+                    self.highlighted_arr_len,
+                    self.highlighted_arr_len,
+                )
+            });
+        }
+    }
+}
+
+/// Builds a snippet of a stored text field for every address, in the same
+/// order. Fragments are at most `max_num_chars` long.
+///
+/// The snippets are released with `burkaz_free_query_runner_snippets`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_snippets(
+    query_runner_ptr: *const c_void,
+    field_id: u32,
+    addr_arr_ptr: *const u64,
+    addr_arr_len: usize,
+    max_num_chars: usize,
+    result_arr_ptr: *mut *const CBurkazSnippet,
+    result_arr_len_ptr: *mut usize,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        if addr_arr_ptr.is_null() || addr_arr_len == 0 {
+            return ok!();
+        }
+        let addrs = unsafe { std::slice::from_raw_parts(addr_arr_ptr, addr_arr_len) }
+            .iter()
+            .map(|addr| BurkazObjectAddr::from(*addr))
+            .collect::<Vec<_>>();
+        let snippets = query_runner
+            .snippets(field_id, &addrs, max_num_chars)?
+            .into_iter()
+            .map(|snippet| {
+                let highlighted = snippet
+                    .highlighted
+                    .into_iter()
+                    .map(|range| CBurkazHighlightRange {
+                        start: range.start,
+                        end: range.end,
+                    })
+                    .collect::<Vec<_>>();
+                let highlighted_arr_len = highlighted.len();
+                let highlighted_arr_ptr = if highlighted.is_empty() {
+                    std::ptr::null_mut()
+                } else {
+                    Box::into_raw(highlighted.into_boxed_slice()).cast()
+                };
+                CBurkazSnippet {
+                    fragment_ptr: CString::new(snippet.fragment)
+                        .map_or(std::ptr::null_mut(), CString::into_raw),
+                    highlighted_arr_ptr,
+                    highlighted_arr_len,
+                }
+            })
+            .collect::<Vec<_>>();
+        unsafe {
+            let boxed_snippets = snippets.into_boxed_slice();
+            *result_arr_len_ptr = boxed_snippets.len();
+            *result_arr_ptr = Box::into_raw(boxed_snippets).cast();
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_query_runner_snippets(
+    result_arr_ptr: *const CBurkazSnippet,
+    result_arr_len: usize,
+) {
+    if !result_arr_ptr.is_null() {
+        drop(unsafe {
+            Vec::from_raw_parts(result_arr_ptr.cast_mut(), result_arr_len, result_arr_len)
+        });
+    }
+}
+
+/// The buckets are released with `burkaz_free_query_runner_terms_aggregation`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_terms_aggregation(
+    query_runner_ptr: *const c_void,
+    field_id: u32,
+    size: u32,
+    min_doc_count: u64,
+    result_arr_ptr: *mut *const CBurkazTermsBucket,
+    result_arr_len_ptr: *mut usize,
+    sum_other_doc_count_ptr: *mut u64,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        let result = query_runner.terms_aggregation(&BurkazTermsAggregation {
+            field_id,
+            size,
+            min_doc_count,
+        })?;
+        unsafe {
+            *sum_other_doc_count_ptr = result.sum_other_doc_count;
+        }
+        if result.buckets.is_empty() {
+            return ok!();
+        }
+        let buckets = result
+            .buckets
+            .into_iter()
+            .map(|bucket| CBurkazTermsBucket {
+                key: bucket.key.into(),
+                doc_count: bucket.doc_count,
+            })
+            .collect::<Vec<_>>();
+        unsafe {
+            let boxed_buckets = buckets.into_boxed_slice();
+            *result_arr_len_ptr = boxed_buckets.len();
+            *result_arr_ptr = Box::into_raw(boxed_buckets).cast();
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_query_runner_terms_aggregation(
+    result_arr_ptr: *const CBurkazTermsBucket,
+    result_arr_len: usize,
+) {
+    if !result_arr_ptr.is_null() {
+        drop(unsafe {
+            Vec::from_raw_parts(result_arr_ptr.cast_mut(), result_arr_len, result_arr_len)
+        });
+    }
+}
+
+/// Percents are between `0` and `100`, a null array skips the percentiles.
+///
+/// The result is released with `burkaz_free_query_runner_metrics_aggregation`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_metrics_aggregation(
+    query_runner_ptr: *const c_void,
+    field_id: u32,
+    percent_arr_ptr: *const f64,
+    percent_arr_len: usize,
+    result_ptr: *mut CBurkazMetricsAggregationResult,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        let percents = slice_from_ptr!(percent_arr_ptr, percent_arr_len).to_vec();
+        let result =
+            query_runner.metrics_aggregation(&BurkazMetricsAggregation { field_id, percents })?;
+        unsafe {
+            result_ptr.write(result.into());
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_query_runner_metrics_aggregation(
+    result: CBurkazMetricsAggregationResult,
+) {
+    drop(result);
+}
+
+/// Values of date fields are given in microseconds since the Unix epoch.
+///
+/// The result is released with `burkaz_free_query_runner_histogram_aggregation`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_query_runner_histogram_aggregation(
+    query_runner_ptr: *const c_void,
+    request_ptr: *const CBurkazHistogramAggregation,
+    result_ptr: *mut CBurkazHistogramAggregationResult,
+) -> u8 {
+    catch_error!({
+        let query_runner = query_runner_from_ptr!(query_runner_ptr);
+        if request_ptr.is_null() {
+            return Err(BurkazError::NullPointer(
+                "histogram request pointer is null",
+            ));
+        }
+        let request =
+            histogram_aggregation_from_native(unsafe { &*request_ptr }).ok_or_else(|| {
+                BurkazError::InvalidAggregation("unknown sub-aggregation type".to_owned())
+            })?;
+        let result = query_runner.histogram_aggregation(&request)?;
+        unsafe {
+            result_ptr.write(result.into());
+        }
+        ok!()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_free_query_runner_histogram_aggregation(
+    result: CBurkazHistogramAggregationResult,
+) {
+    drop(result);
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn burkaz_query_runner_delete_all(query_runner_ptr: *const c_void) -> u8 {
     catch_error!({
@@ -101,3 +706,77 @@ pub extern "C" fn burkaz_query_runner_delete_all(query_runner_ptr: *const c_void
         query_runner.delete_all()
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use tantivy::{TantivyDocument, schema::Field};
+
+    use super::*;
+    use crate::{
+        index::{BurkazDirectory, BurkazIndex},
+        schema::{
+            BurkazSchema, BurkazSchemaField, BurkazSchemaFieldOptions, BurkazSchemaFieldType,
+        },
+        term::BurkazTerm,
+    };
+
+    #[test]
+    fn search_scored_pages_through_scores() {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "title".to_owned(),
+            BurkazSchemaFieldOptions {
+                fieldnorms: true,
+                ..BurkazSchemaFieldOptions::for_test(BurkazSchemaFieldType::Text)
+            },
+        ));
+        let index =
+            BurkazIndex::new("posts".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+        let objects = ["rust and more", "rust", "rust rust", "go"]
+            .into_iter()
+            .map(|title| {
+                let mut object = TantivyDocument::new();
+                object.add_text(Field::from_field_id(0), title);
+                object
+            })
+            .collect();
+        index.add_all(objects).unwrap();
+
+        let query = BurkazQuery::Term {
+            term: BurkazTerm::new(0, &"rust".into()).unwrap(),
+            indexing_strategy: Default::default(),
+        };
+        let query_runner_ptr = QueryRunner::new(index.downgrade(), query)
+            .into_raw()
+            .cast::<c_void>()
+            .cast_const();
+
+        let search = |offset, limit| {
+            let mut result_arr_ptr = std::ptr::null();
+            let mut result_arr_len = 0;
+            let status = burkaz_query_runner_search_scored(
+                query_runner_ptr,
+                offset,
+                limit,
+                &mut result_arr_ptr,
+                &mut result_arr_len,
+            );
+            assert_eq!(status, 0);
+            let result: Vec<_> = slice_from_ptr!(result_arr_ptr, result_arr_len)
+                .iter()
+                .map(|hit| (hit.addr, hit.score))
+                .collect();
+            burkaz_free_query_runner_search_scored_result(result_arr_ptr, result_arr_len);
+            result
+        };
+
+        let all = search(0, 10);
+        assert_eq!(all.len(), 3);
+        assert!(all.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert_eq!(search(1, 1), all[1..2]);
+        assert_eq!(search(2, 10), all[2..]);
+        assert!(search(3, 10).is_empty());
+
+        burkaz_free_query_runner(query_runner_ptr);
+    }
+}
@@ -1,11 +1,14 @@
 use std::ffi::{c_char, c_void};
 
 use crate::{
+    analyzer::{BurkazAnalyzer, BurkazLanguage, BurkazTokenFilter, BurkazTokenizer},
+    error::BurkazError,
+    native::native_query_parser::{CBurkazQueryParserOptions, query_parser_options_from_native},
     schema::{
-        BurkazIndexingStrategy, BurkazSchema, BurkazSchemaField, BurkazSchemaFieldOptions,
-        BurkazSchemaFieldType,
+        BurkazDatePrecision, BurkazIndexingStrategy, BurkazSchema, BurkazSchemaField,
+        BurkazSchemaFieldOptions, BurkazSchemaFieldType,
     },
-    str_from_ptr,
+    slice_from_ptr, str_from_ptr,
 };
 
 #[repr(C)]
@@ -21,6 +24,8 @@ pub struct CBurkazSchemaFieldOptions {
     pub fast_tokenizer_len: usize,
     pub indexing_tokenizer_ptr: *const c_char,
     pub indexing_tokenizer_len: usize,
+    pub unique: u8,
+    pub date_precision: u8,
 }
 
 #[repr(C)]
@@ -30,6 +35,49 @@ pub struct CBurkazSchemaField {
     pub options_ptr: *const CBurkazSchemaFieldOptions,
 }
 
+#[repr(C)]
+pub struct CBurkazWord {
+    pub ptr: *const c_char,
+    pub len: usize,
+}
+
+/// Tokenizer types: `1` simple, `2` whitespace, `3` raw, `4` n-gram reading
+/// the gram sizes and `prefix_only`, `5` regex reading the pattern.
+#[repr(C)]
+pub struct CBurkazTokenizer {
+    pub typ: u8,
+    pub min_gram: usize,
+    pub max_gram: usize,
+    pub prefix_only: u8,
+    pub pattern_ptr: *const c_char,
+    pub pattern_len: usize,
+}
+
+/// Filter types: `1` lowercase, `2` ASCII folding, `3` alphanumeric only,
+/// `4` stop words of `language`, `5` stop words from `word_arr_ptr`, `6` stemmer
+/// of `language`, `7` remove tokens of at least `length_limit` bytes, `8` split
+/// compound words made of `word_arr_ptr`, `9` edge n-grams of `min_gram` to
+/// `max_gram` characters, `10` lowercase with the Turkish casing rules.
+#[repr(C)]
+pub struct CBurkazTokenFilter {
+    pub typ: u8,
+    pub language: u8,
+    pub length_limit: usize,
+    pub word_arr_ptr: *const CBurkazWord,
+    pub word_arr_len: usize,
+    pub min_gram: usize,
+    pub max_gram: usize,
+}
+
+#[repr(C)]
+pub struct CBurkazAnalyzer {
+    pub name_ptr: *const c_char,
+    pub name_len: usize,
+    pub tokenizer: CBurkazTokenizer,
+    pub filter_arr_ptr: *const CBurkazTokenFilter,
+    pub filter_arr_len: usize,
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn burkaz_schema_new(
     field_array_ptr: *const *const CBurkazSchemaField,
@@ -57,11 +105,120 @@ pub extern "C" fn burkaz_schema_new(
     schema.into_raw().cast()
 }
 
+/// Sets the options used to build the index query parser when the schema is
+/// opened. A null options pointer restores the defaults.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_schema_set_query_parser_options(
+    schema_ptr: *const c_void,
+    options_ptr: *const CBurkazQueryParserOptions,
+) {
+    if schema_ptr.is_null() {
+        return;
+    }
+    let schema = unsafe { &mut *(schema_ptr as *mut BurkazSchema) };
+
+    let options = if options_ptr.is_null() {
+        Default::default()
+    } else {
+        query_parser_options_from_native(unsafe { &*options_ptr })
+    };
+
+    schema.set_query_parser_options(options);
+}
+
+/// Adds an analyzer registered on the index when the schema is opened. Fails
+/// for unknown tokenizer, filter or language codes.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_schema_add_analyzer(
+    schema_ptr: *const c_void,
+    analyzer_ptr: *const CBurkazAnalyzer,
+) -> u8 {
+    catch_error!({
+        if schema_ptr.is_null() {
+            return Err(BurkazError::NullPointer("schema pointer is null"));
+        }
+        if analyzer_ptr.is_null() {
+            return Err(BurkazError::NullPointer("analyzer pointer is null"));
+        }
+        let schema = unsafe { &mut *(schema_ptr as *mut BurkazSchema) };
+
+        let analyzer = analyzer_from_native(unsafe { &*analyzer_ptr })?;
+        schema.add_analyzer(analyzer);
+
+        ok!()
+    })
+}
+
+fn analyzer_from_native(c_analyzer: &CBurkazAnalyzer) -> crate::Result<BurkazAnalyzer> {
+    if c_analyzer.name_ptr.is_null() {
+        return Err(BurkazError::NullPointer("analyzer name pointer is null"));
+    }
+    let name = str_from_ptr!(c_analyzer.name_ptr, c_analyzer.name_len).to_owned();
+    let invalid = |what: &str| BurkazError::InvalidSchema(format!("analyzer `{name}`: {what}"));
+
+    let c_tokenizer = &c_analyzer.tokenizer;
+    let tokenizer = match c_tokenizer.typ {
+        1 => BurkazTokenizer::Simple,
+        2 => BurkazTokenizer::Whitespace,
+        3 => BurkazTokenizer::Raw,
+        4 => BurkazTokenizer::Ngram {
+            min_gram: c_tokenizer.min_gram,
+            max_gram: c_tokenizer.max_gram,
+            prefix_only: c_tokenizer.prefix_only != 0,
+        },
+        5 if !c_tokenizer.pattern_ptr.is_null() => BurkazTokenizer::Regex(
+            str_from_ptr!(c_tokenizer.pattern_ptr, c_tokenizer.pattern_len).to_owned(),
+        ),
+        _ => return Err(invalid("unknown tokenizer")),
+    };
+
+    let c_filters = slice_from_ptr!(c_analyzer.filter_arr_ptr, c_analyzer.filter_arr_len);
+    let filters = c_filters
+        .iter()
+        .map(|c_filter| {
+            let language = || {
+                BurkazLanguage::from_code(c_filter.language)
+                    .ok_or_else(|| invalid("unknown language"))
+            };
+            let words = || {
+                slice_from_ptr!(c_filter.word_arr_ptr, c_filter.word_arr_len)
+                    .iter()
+                    .map(|word| str_from_ptr!(word.ptr, word.len).to_owned())
+                    .collect()
+            };
+            Ok(match c_filter.typ {
+                1 => BurkazTokenFilter::LowerCase,
+                2 => BurkazTokenFilter::AsciiFolding,
+                3 => BurkazTokenFilter::AlphaNumOnly,
+                4 => BurkazTokenFilter::StopWords(language()?),
+                5 => BurkazTokenFilter::StopWordList(words()),
+                6 => BurkazTokenFilter::Stemmer(language()?),
+                7 => BurkazTokenFilter::RemoveLong(c_filter.length_limit),
+                8 => BurkazTokenFilter::SplitCompound(words()),
+                9 => BurkazTokenFilter::EdgeNgram {
+                    min_gram: c_filter.min_gram,
+                    max_gram: c_filter.max_gram,
+                },
+                10 => BurkazTokenFilter::TurkishLowerCase,
+                _ => return Err(invalid("unknown filter")),
+            })
+        })
+        .collect::<crate::Result<_>>()?;
+
+    Ok(BurkazAnalyzer::new(name, tokenizer, filters))
+}
+
 fn options_from_native(c_options: &CBurkazSchemaFieldOptions) -> Option<BurkazSchemaFieldOptions> {
     let typ = match c_options.typ {
         1 => BurkazSchemaFieldType::Int64,
         2 => BurkazSchemaFieldType::Text,
         3 => BurkazSchemaFieldType::Boolean,
+        4 => BurkazSchemaFieldType::F64,
+        5 => BurkazSchemaFieldType::U64,
+        6 => BurkazSchemaFieldType::Date,
+        7 => BurkazSchemaFieldType::Bytes,
+        8 => BurkazSchemaFieldType::Json,
+        9 => BurkazSchemaFieldType::Facet,
         _ => return None,
     };
 
@@ -90,11 +247,13 @@ fn options_from_native(c_options: &CBurkazSchemaFieldOptions) -> Option<BurkazSc
     Some(BurkazSchemaFieldOptions {
         typ: typ,
         fast: as_bool!(c_options.fast),
+        unique: as_bool!(c_options.unique),
         stored: as_bool!(c_options.stored),
         coerce: as_bool!(c_options.coerce),
         indexed: as_bool!(c_options.indexed),
         fieldnorms: as_bool!(c_options.fieldnorms),
         indexing_strategy: indexing_strategy,
+        date_precision: BurkazDatePrecision::from_code(c_options.date_precision),
         fast_tokenizer: as_optional_string!(
             c_options.fast_tokenizer_ptr,
             c_options.fast_tokenizer_len
@@ -1,6 +1,11 @@
 use std::ffi::{c_char, c_void};
 
-use crate::term::BurkazTerm;
+use tantivy::{
+    DateTime,
+    schema::{Facet, document::ReferenceValueLeaf},
+};
+
+use crate::{slice_from_ptr, str_from_ptr, term::BurkazTerm};
 
 #[macro_export]
 macro_rules! term_from_ptr {
@@ -9,16 +14,28 @@ macro_rules! term_from_ptr {
     };
 }
 
+/// Hands the term over to the caller, or returns a null pointer when it could not
+/// be built.
+fn term_into_raw(term: crate::Result<BurkazTerm>) -> *const c_void {
+    match term {
+        Ok(term) => term.into_raw().cast(),
+        Err(error) => {
+            crate::error::store_error(error);
+            std::ptr::null()
+        }
+    }
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn burkaz_term_int(field_id: u32, value: i64) -> *const c_void {
     let term = BurkazTerm::new(field_id, &value.into());
-    Box::new(term).into_raw().cast()
+    term_into_raw(term)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn burkaz_term_boolean(field_id: u32, value: bool) -> *const c_void {
     let term = BurkazTerm::new(field_id, &value.into());
-    Box::new(term).into_raw().cast()
+    term_into_raw(term)
 }
 
 #[unsafe(no_mangle)]
@@ -31,5 +48,119 @@ pub extern "C" fn burkaz_term_text(
         std::str::from_utf8_unchecked(std::slice::from_raw_parts(value_ptr.cast(), value_len))
     };
     let term = BurkazTerm::new(field_id, &value.into());
-    Box::new(term).into_raw().cast()
+    term_into_raw(term)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_term_f64(field_id: u32, value: f64) -> *const c_void {
+    let term = BurkazTerm::new(field_id, &value.into());
+    term_into_raw(term)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_term_u64(field_id: u32, value: u64) -> *const c_void {
+    let term = BurkazTerm::new(field_id, &value.into());
+    term_into_raw(term)
+}
+
+/// The date is given in microseconds since the Unix epoch.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_term_date(field_id: u32, value: i64) -> *const c_void {
+    let value = DateTime::from_timestamp_micros(value);
+    let term = BurkazTerm::new(field_id, &value.into());
+    term_into_raw(term)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_term_bytes(
+    field_id: u32,
+    value_ptr: *const u8,
+    value_len: usize,
+) -> *const c_void {
+    let value: &[u8] = slice_from_ptr!(value_ptr, value_len);
+    let term = BurkazTerm::new(field_id, &value.into());
+    term_into_raw(term)
+}
+
+/// Terms on JSON fields address the value through a dot separated path, e.g.
+/// `color` or `size.width`.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_term_json_int(
+    field_id: u32,
+    path_ptr: *const c_char,
+    path_len: usize,
+    value: i64,
+) -> *const c_void {
+    let path = str_from_ptr!(path_ptr, path_len);
+    let term = BurkazTerm::new_json(field_id, path, &value.into());
+    term_into_raw(term)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_term_json_boolean(
+    field_id: u32,
+    path_ptr: *const c_char,
+    path_len: usize,
+    value: bool,
+) -> *const c_void {
+    let path = str_from_ptr!(path_ptr, path_len);
+    let term = BurkazTerm::new_json(field_id, path, &value.into());
+    term_into_raw(term)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_term_json_text(
+    field_id: u32,
+    path_ptr: *const c_char,
+    path_len: usize,
+    value_ptr: *const c_char,
+    value_len: usize,
+) -> *const c_void {
+    let path = str_from_ptr!(path_ptr, path_len);
+    let value = str_from_ptr!(value_ptr, value_len);
+    let term = BurkazTerm::new_json(field_id, path, &value.into());
+    term_into_raw(term)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_term_json_f64(
+    field_id: u32,
+    path_ptr: *const c_char,
+    path_len: usize,
+    value: f64,
+) -> *const c_void {
+    let path = str_from_ptr!(path_ptr, path_len);
+    let term = BurkazTerm::new_json(field_id, path, &value.into());
+    term_into_raw(term)
+}
+
+/// The date is given in microseconds since the Unix epoch.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_term_json_date(
+    field_id: u32,
+    path_ptr: *const c_char,
+    path_len: usize,
+    value: i64,
+) -> *const c_void {
+    let path = str_from_ptr!(path_ptr, path_len);
+    let value = DateTime::from_timestamp_micros(value);
+    let term = BurkazTerm::new_json(field_id, path, &value.into());
+    term_into_raw(term)
+}
+
+/// Returns a null pointer when the path is not a valid facet, e.g. `/electronics/phones`.
+///
+/// Facets index every ancestor, so the term also matches the objects filed under
+/// a descendant of the facet.
+#[unsafe(no_mangle)]
+pub extern "C" fn burkaz_term_facet(
+    field_id: u32,
+    path_ptr: *const c_char,
+    path_len: usize,
+) -> *const c_void {
+    let Ok(facet) = Facet::from_text(str_from_ptr!(path_ptr, path_len)) else {
+        return std::ptr::null();
+    };
+    let term = BurkazTerm::new(field_id, &ReferenceValueLeaf::Facet(facet.encoded_str()));
+    term_into_raw(term)
 }
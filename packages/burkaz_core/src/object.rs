@@ -6,7 +6,7 @@ use std::{
 
 use byteorder::{ReadBytesExt, WriteBytesExt};
 use tantivy::{
-    Document,
+    DateTime, Document,
     schema::{
         Field, OwnedValue, Value,
         document::{DocumentDeserialize, ReferenceValue, ReferenceValueLeaf},
@@ -21,6 +21,28 @@ pub enum ValueType {
     Int64 = 1,
     Text = 2,
     Boolean = 3,
+    F64 = 4,
+    U64 = 5,
+    Date = 6,
+    Bytes = 7,
+    Facet = 8,
+}
+
+impl ValueType {
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(ValueType::Null),
+            1 => Some(ValueType::Int64),
+            2 => Some(ValueType::Text),
+            3 => Some(ValueType::Boolean),
+            4 => Some(ValueType::F64),
+            5 => Some(ValueType::U64),
+            6 => Some(ValueType::Date),
+            7 => Some(ValueType::Bytes),
+            8 => Some(ValueType::Facet),
+            _ => None,
+        }
+    }
 }
 
 type Addr = u32;
@@ -117,6 +139,26 @@ impl BurkazObject {
                 write_type!(Boolean);
                 write_value!(value);
             }
+            OwnedValue::F64(value) => {
+                write_type!(F64);
+                write_value!(value);
+            }
+            OwnedValue::U64(value) => {
+                write_type!(U64);
+                write_value!(value);
+            }
+            OwnedValue::Date(value) => {
+                write_type!(Date);
+                write_value!(&value.into_timestamp_nanos());
+            }
+            OwnedValue::Bytes(value) => {
+                write_type!(Bytes);
+                write_value!(value);
+            }
+            OwnedValue::Facet(value) => {
+                write_type!(Facet);
+                write_value!(&value.encoded_str().to_owned());
+            }
             _ => return,
         }
 
@@ -193,14 +235,7 @@ impl<'a> BurkazValueRef<'a> {
     }
 
     pub fn typ(&self) -> ValueType {
-        let type_code = self.0.as_ref()[0];
-        match type_code {
-            0 => ValueType::Null,
-            1 => ValueType::Int64,
-            2 => ValueType::Text,
-            3 => ValueType::Boolean,
-            _ => ValueType::Null,
-        }
+        ValueType::from_code(self.0.as_ref()[0]).unwrap_or_default()
     }
 
     pub fn as_int(&self) -> Option<i64> {
@@ -225,6 +260,47 @@ impl<'a> BurkazValueRef<'a> {
         }
         Some(self.0.as_ref().get(1)? == &1u8)
     }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        if self.typ() != ValueType::F64 {
+            return None;
+        }
+        Some(f64::from_bits(read_u64_le_from_bytes(self.0, 1)?))
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        if self.typ() != ValueType::U64 {
+            return None;
+        }
+        read_u64_le_from_bytes(self.0, 1)
+    }
+
+    pub fn as_date(&self) -> Option<DateTime> {
+        if self.typ() != ValueType::Date {
+            return None;
+        }
+        Some(DateTime::from_timestamp_nanos(read_i64_le_from_bytes(
+            self.0, 1,
+        )?))
+    }
+
+    pub fn as_bytes(&self) -> Option<&'a [u8]> {
+        if self.typ() != ValueType::Bytes {
+            return None;
+        }
+        let len = read_u64_le_from_bytes(self.0, 1)? as usize;
+        self.0.get(9..9 + len)
+    }
+
+    /// Returns the facet in its encoded form, see [`tantivy::schema::Facet::encoded_str`].
+    pub fn as_facet(&self) -> Option<&'a str> {
+        if self.typ() != ValueType::Facet {
+            return None;
+        }
+        let len = read_u64_le_from_bytes(self.0, 1)? as usize;
+        let str_bytes = self.0.get(9..9 + len)?;
+        std::str::from_utf8(str_bytes).ok()
+    }
 }
 
 impl<'a> Value<'a> for BurkazValueRef<'a> {
@@ -237,6 +313,11 @@ impl<'a> Value<'a> for BurkazValueRef<'a> {
             ValueType::Int64 => ReferenceValueLeaf::I64(self.as_int().unwrap()).into(),
             ValueType::Text => ReferenceValueLeaf::Str(self.as_text().unwrap()).into(),
             ValueType::Boolean => ReferenceValueLeaf::Bool(self.as_bool().unwrap()).into(),
+            ValueType::F64 => ReferenceValueLeaf::F64(self.as_f64().unwrap()).into(),
+            ValueType::U64 => ReferenceValueLeaf::U64(self.as_u64().unwrap()).into(),
+            ValueType::Date => ReferenceValueLeaf::Date(self.as_date().unwrap()).into(),
+            ValueType::Bytes => ReferenceValueLeaf::Bytes(self.as_bytes().unwrap()).into(),
+            ValueType::Facet => ReferenceValueLeaf::Facet(self.as_facet().unwrap()).into(),
         }
     }
 }
@@ -293,17 +374,13 @@ impl BinarySerializable for ValueType {
     }
 
     fn deserialize<R: Read>(reader: &mut R) -> std::io::Result<Self> {
-        const VALUE_TYPE_COUNT: u8 = 3;
         let num = u8::deserialize(reader)?;
-        let type_code = if (0..=VALUE_TYPE_COUNT).contains(&num) {
-            unsafe { std::mem::transmute::<u8, ValueType>(num) }
-        } else {
-            return Err(std::io::Error::new(
+        ValueType::from_code(num).ok_or_else(|| {
+            std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 format!("Invalid value type id: {num}"),
-            ));
-        };
-        Ok(type_code)
+            )
+        })
     }
 }
 
@@ -419,9 +496,28 @@ mod tests {
         let value = BurkazValueRef(&[3]);
         assert_eq!(value.typ(), ValueType::Boolean);
         let value = BurkazValueRef(&[4]);
+        assert_eq!(value.typ(), ValueType::F64);
+        let value = BurkazValueRef(&[5]);
+        assert_eq!(value.typ(), ValueType::U64);
+        let value = BurkazValueRef(&[6]);
+        assert_eq!(value.typ(), ValueType::Date);
+        let value = BurkazValueRef(&[7]);
+        assert_eq!(value.typ(), ValueType::Bytes);
+        let value = BurkazValueRef(&[8]);
+        assert_eq!(value.typ(), ValueType::Facet);
+        let value = BurkazValueRef(&[9]);
         assert_eq!(value.typ(), ValueType::Null); // invalid type code
     }
 
+    #[test]
+    fn value_type_deserialize() {
+        for code in 0..=8u8 {
+            let value_type = ValueType::deserialize(&mut &[code][..]).unwrap();
+            assert_eq!(value_type as u8, code);
+        }
+        assert!(ValueType::deserialize(&mut &[9u8][..]).is_err());
+    }
+
     #[test]
     fn burkaz_value_ref_as_int() {
         let bytes = &[1, 0, 0, 0, 0, 0, 0, 0, 0];
@@ -445,4 +541,37 @@ mod tests {
             Some(unsafe { str::from_utf8_unchecked(&[95]) })
         );
     }
+
+    #[test]
+    fn burkaz_value_ref_as_numbers() {
+        let mut object = BurkazObject::default();
+        object.write_value(0, &OwnedValue::F64(1.5));
+        object.write_value(1, &OwnedValue::U64(u64::MAX));
+        object.write_value(
+            2,
+            &OwnedValue::Date(DateTime::from_timestamp_micros(1_700_000_000_123_456)),
+        );
+
+        let value = object.field_values(0).next().unwrap();
+        assert_eq!(value.as_f64(), Some(1.5));
+        assert_eq!(value.as_u64(), None);
+
+        let value = object.field_values(1).next().unwrap();
+        assert_eq!(value.as_u64(), Some(u64::MAX));
+
+        let value = object.field_values(2).next().unwrap();
+        assert_eq!(
+            value.as_date(),
+            Some(DateTime::from_timestamp_micros(1_700_000_000_123_456))
+        );
+    }
+
+    #[test]
+    fn burkaz_value_ref_as_bytes() {
+        let bytes = &[7, 2, 0, 0, 0, 0, 0, 0, 0, 0xca, 0xfe];
+        let value = BurkazValueRef(bytes);
+        assert_eq!(value.typ(), ValueType::Bytes);
+        assert_eq!(value.as_bytes(), Some(&[0xca, 0xfe][..]));
+        assert_eq!(value.as_text(), None);
+    }
 }
@@ -1,9 +1,19 @@
-use tantivy::query::{
-    AllQuery, BooleanQuery, BoostQuery, EmptyQuery, FuzzyTermQuery, Occur, PhraseQuery, Query,
-    RegexPhraseQuery, TermQuery, TermSetQuery,
+use std::ops::Bound;
+
+use tantivy::{
+    query::{
+        AllQuery, BooleanQuery, BoostQuery, EmptyQuery, FuzzyTermQuery, Occur, PhraseQuery, Query,
+        QueryParser, RangeQuery, RegexPhraseQuery, TermQuery, TermSetQuery,
+    },
+    query_grammar,
+    schema::{Field, FieldType, IndexRecordOption, Term},
 };
 
-use crate::{index::BurkazIndex, schema::BurkazIndexingStrategy, term::BurkazTerm};
+use crate::{
+    analyzer::BurkazAnalyzer, error::BurkazError, index::BurkazIndex,
+    query_parser::BurkazQueryParserOptions, query_runner::field_entry,
+    schema::BurkazIndexingStrategy, term::BurkazTerm,
+};
 
 /// A query that can be executed against the index.
 pub enum BurkazQuery {
@@ -15,8 +25,20 @@ pub enum BurkazQuery {
     And(Vec<BurkazQuery>),
     /// At least one child query must match (OR).
     Or(Vec<BurkazQuery>),
-    /// The child query must not match (NOT).
+    /// The child query must not match (NOT), matches every other document.
     Not(Box<BurkazQuery>),
+    /// Combines clauses like a boolean query of Elasticsearch.
+    ///
+    /// Every `must` query has to match and no `must_not` query may match. At
+    /// least `minimum_should_match` of the `should` queries have to match, by
+    /// default one when there are only `should` queries and none otherwise.
+    /// Queries with only `must_not` clauses match every other document.
+    Boolean {
+        must: Vec<BurkazQuery>,
+        should: Vec<BurkazQuery>,
+        must_not: Vec<BurkazQuery>,
+        minimum_should_match: Option<usize>,
+    },
     /// Matches a term.
     Term {
         term: BurkazTerm,
@@ -33,6 +55,14 @@ pub enum BurkazQuery {
         transposition_cost_one: bool,
         prefix: bool,
     },
+    /// Matches terms between the lower and upper bounds.
+    ///
+    /// Both bounds must target the same field. When both bounds are unbounded
+    /// the query matches all documents.
+    Range {
+        lower: Bound<BurkazTerm>,
+        upper: Bound<BurkazTerm>,
+    },
     /// Matches a phrase.
     Phase {
         terms: Vec<BurkazTerm>,
@@ -48,8 +78,27 @@ pub enum BurkazQuery {
         query: Box<BurkazQuery>,
         boost: f32,
     },
+    /// Search-as-you-type query, every word of the text must start a word of
+    /// the field.
+    ///
+    /// The field must be an indexed text field. Prefixes are looked up as plain
+    /// terms instead of being expanded at search time, so the field should use
+    /// the [`crate::analyzer::AUTOCOMPLETE_ANALYZER_NAME`] analyzer, otherwise
+    /// words only match whole indexed words.
+    Autocomplete {
+        field_id: u32,
+        text: String,
+    },
+    /// Parses a query string with the index query parser.
+    ///
+    /// Strict queries fail on the first parse error. Lenient queries keep
+    /// whatever could be parsed and report the errors separately.
+    ///
+    /// The index query parser is used unless options are given.
     Parse {
         query_text: String,
+        lenient: bool,
+        options: Option<BurkazQueryParserOptions>,
     },
 }
 
@@ -63,39 +112,100 @@ impl BurkazQuery {
     }
 }
 
+/// An error found while parsing a query string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BurkazQueryParseError {
+    /// Byte offset of the error in the query string, when known.
+    pub position: Option<usize>,
+    pub message: String,
+}
+
+impl From<BurkazQueryParseError> for BurkazError {
+    fn from(error: BurkazQueryParseError) -> Self {
+        BurkazError::QueryParse {
+            position: error.position,
+            message: error.message,
+        }
+    }
+}
+
 impl BurkazQuery {
-    pub fn to_tantivy_query(&self, index: &BurkazIndex) -> Box<dyn Query> {
-        match self {
+    /// Converts the query, failing on the first parse error of a strict
+    /// `Parse` query.
+    pub fn to_tantivy_query(&self, index: &BurkazIndex) -> crate::Result<Box<dyn Query>> {
+        self.build(index, &mut Vec::new())
+    }
+
+    /// Converts the query and returns the parse errors of every lenient `Parse`
+    /// query alongside it.
+    pub fn to_tantivy_query_lenient(
+        &self,
+        index: &BurkazIndex,
+    ) -> crate::Result<(Box<dyn Query>, Vec<BurkazQueryParseError>)> {
+        let mut errors = Vec::new();
+        let query = self.build(index, &mut errors)?;
+        Ok((query, errors))
+    }
+
+    fn build(
+        &self,
+        index: &BurkazIndex,
+        errors: &mut Vec<BurkazQueryParseError>,
+    ) -> crate::Result<Box<dyn Query>> {
+        Ok(match self {
             Self::All => Box::new(AllQuery),
             Self::Empty => Box::new(EmptyQuery),
-            Self::And(queries) => Box::new(BooleanQuery::new(
+            Self::And(queries) => boolean_query(
                 queries
                     .iter()
-                    .map(|query| (Occur::Must, query.to_tantivy_query(index)))
-                    .collect(),
-            )),
+                    .map(|query| match query {
+                        // Negations are folded in the conjunction rather than each
+                        // matching everything else on its own.
+                        Self::Not(query) => Ok((Occur::MustNot, query.build(index, errors)?)),
+                        query => Ok((Occur::Must, query.build(index, errors)?)),
+                    })
+                    .collect::<crate::Result<_>>()?,
+                None,
+            ),
             Self::Or(queries) => Box::new(BooleanQuery::new(
                 queries
                     .iter()
-                    .map(|query| (Occur::Should, query.to_tantivy_query(index)))
-                    .collect(),
+                    .map(|query| Ok((Occur::Should, query.build(index, errors)?)))
+                    .collect::<crate::Result<_>>()?,
             )),
-            Self::Not(query) => Box::new(BooleanQuery::new(vec![(
-                Occur::MustNot,
-                query.to_tantivy_query(index),
-            )])),
+            Self::Not(query) => {
+                boolean_query(vec![(Occur::MustNot, query.build(index, errors)?)], None)
+            }
+            Self::Boolean {
+                must,
+                should,
+                must_not,
+                minimum_should_match,
+            } => {
+                let mut clauses = Vec::with_capacity(must.len() + should.len() + must_not.len());
+                for (occur, queries) in [
+                    (Occur::Must, must),
+                    (Occur::Should, should),
+                    (Occur::MustNot, must_not),
+                ] {
+                    for query in queries {
+                        clauses.push((occur, query.build(index, errors)?));
+                    }
+                }
+                boolean_query(clauses, *minimum_should_match)
+            }
             Self::Term {
                 term,
                 indexing_strategy,
             } => Box::new(TermQuery::new(
-                term.to_tantivy_term(),
+                term.to_tantivy_search_term()?,
                 (*indexing_strategy).into(),
             )),
             Self::TermSet { terms } => Box::new(TermSetQuery::new(
                 terms
                     .iter()
-                    .map(|term| term.to_tantivy_term())
-                    .collect::<Vec<_>>(),
+                    .map(|term| term.to_tantivy_search_term())
+                    .collect::<crate::Result<Vec<_>>>()?,
             )),
             Self::FuzzyTerm {
                 term,
@@ -105,24 +215,39 @@ impl BurkazQuery {
             } => {
                 if *prefix {
                     Box::new(FuzzyTermQuery::new_prefix(
-                        term.to_tantivy_term(),
+                        term.to_tantivy_term()?,
                         *distance,
                         *transposition_cost_one,
                     ))
                 } else {
                     Box::new(FuzzyTermQuery::new(
-                        term.to_tantivy_term(),
+                        term.to_tantivy_term()?,
                         *distance,
                         *transposition_cost_one,
                     ))
                 }
             }
+            Self::Range { lower, upper } => match (lower, upper) {
+                (Bound::Unbounded, Bound::Unbounded) => Box::new(AllQuery),
+                (
+                    Bound::Included(lower) | Bound::Excluded(lower),
+                    Bound::Included(upper) | Bound::Excluded(upper),
+                ) if lower.field_id() != upper.field_id() => {
+                    return Err(BurkazError::InvalidQuery(
+                        "range bounds target different fields".to_owned(),
+                    ));
+                }
+                _ => Box::new(RangeQuery::new(
+                    tantivy_bound(lower)?,
+                    tantivy_bound(upper)?,
+                )),
+            },
             Self::Phase { terms, slop } => Box::new(PhraseQuery::new_with_offset_and_slop(
                 terms
                     .iter()
                     .enumerate()
-                    .map(|(index, term)| (index, term.to_tantivy_term()))
-                    .collect(),
+                    .map(|(index, term)| Ok((index, term.to_tantivy_term()?)))
+                    .collect::<crate::Result<_>>()?,
                 *slop,
             )),
             Self::RegexPhase {
@@ -140,16 +265,442 @@ impl BurkazQuery {
                 query
             }
             Self::Boost { query, boost } => {
-                Box::new(BoostQuery::new(query.to_tantivy_query(index), *boost))
+                Box::new(BoostQuery::new(query.build(index, errors)?, *boost))
             }
-            Self::Parse { query_text } => {
-                let parsed_query = index
-                    .query_parser()
-                    .parse_query(&query_text)
-                    .unwrap_or(Box::new(EmptyQuery));
+            Self::Autocomplete { field_id, text } => {
+                let entry = field_entry(&index.underlying_index().schema(), *field_id)?;
+                let indexed = match entry.field_type() {
+                    FieldType::Str(options) => options.get_indexing_options().is_some(),
+                    _ => false,
+                };
+                if !indexed {
+                    return Err(BurkazError::InvalidField(format!(
+                        "cannot autocomplete field `{}`, it is not an indexed text field",
+                        entry.name()
+                    )));
+                }
 
+                let field = Field::from_field_id(*field_id);
+                let words = BurkazAnalyzer::autocomplete_words(text);
+                if words.is_empty() {
+                    return Ok(Box::new(EmptyQuery));
+                }
+                Box::new(BooleanQuery::new(
+                    words
+                        .iter()
+                        .map(|word| {
+                            let term = Term::from_field_text(field, word);
+                            let query: Box<dyn Query> =
+                                Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs));
+                            (Occur::Must, query)
+                        })
+                        .collect(),
+                ))
+            }
+            Self::Parse {
+                query_text,
+                lenient,
+                options,
+            } => {
+                let (parsed_query, mut parse_errors) = match options {
+                    Some(options) => {
+                        let parser = options.build(index.underlying_index())?;
+                        parse(&parser, query_text)
+                    }
+                    None => parse(index.query_parser(), query_text),
+                };
+                if !*lenient && !parse_errors.is_empty() {
+                    return Err(parse_errors.swap_remove(0).into());
+                }
+                errors.append(&mut parse_errors);
                 parsed_query
             }
+        })
+    }
+}
+
+fn tantivy_bound(bound: &Bound<BurkazTerm>) -> crate::Result<Bound<Term>> {
+    Ok(match bound {
+        Bound::Included(term) => Bound::Included(term.to_tantivy_term()?),
+        Bound::Excluded(term) => Bound::Excluded(term.to_tantivy_term()?),
+        Bound::Unbounded => Bound::Unbounded,
+    })
+}
+
+/// Tantivy matches nothing when every clause is negated, so such queries are
+/// combined with [`AllQuery`] to match every other document.
+fn boolean_query(
+    mut clauses: Vec<(Occur, Box<dyn Query>)>,
+    minimum_should_match: Option<usize>,
+) -> Box<dyn Query> {
+    if !clauses.is_empty() && clauses.iter().all(|(occur, _)| *occur == Occur::MustNot) {
+        clauses.push((Occur::Must, Box::new(AllQuery)));
+    }
+
+    Box::new(match minimum_should_match {
+        Some(minimum_should_match) => {
+            BooleanQuery::with_minimum_required_clauses(clauses, minimum_should_match)
         }
+        None => BooleanQuery::new(clauses),
+    })
+}
+
+/// Parses the query text leniently, keeping the position of syntax errors.
+fn parse(parser: &QueryParser, query_text: &str) -> (Box<dyn Query>, Vec<BurkazQueryParseError>) {
+    let (user_input_ast, syntax_errors) = query_grammar::parse_query_lenient(query_text);
+
+    let mut errors = syntax_errors
+        .into_iter()
+        .map(|error| BurkazQueryParseError {
+            position: Some(error.pos),
+            message: error.message,
+        })
+        .collect::<Vec<_>>();
+
+    let (query, query_errors) = parser.build_query_from_user_input_ast_lenient(user_input_ast);
+
+    errors.extend(query_errors.into_iter().map(|error| BurkazQueryParseError {
+        position: None,
+        message: error.to_string(),
+    }));
+
+    (query, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use tantivy::{
+        DateTime, TantivyDocument,
+        schema::{Field, OwnedValue, Value, document::ReferenceValueLeaf},
+    };
+
+    use super::*;
+    use crate::{
+        analyzer::AUTOCOMPLETE_ANALYZER_NAME,
+        index::BurkazDirectory,
+        query_runner::QueryRunner,
+        schema::{
+            BurkazDatePrecision, BurkazSchema, BurkazSchemaField, BurkazSchemaFieldOptions,
+            BurkazSchemaFieldType,
+        },
+    };
+
+    fn price_index() -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "price".to_owned(),
+            BurkazSchemaFieldOptions {
+                fast: true,
+                ..BurkazSchemaFieldOptions::for_test(BurkazSchemaFieldType::Int64)
+            },
+        ));
+        let index =
+            BurkazIndex::new("prices".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+        let objects = (0..100)
+            .map(|price| {
+                let mut object = TantivyDocument::new();
+                object.add_i64(Field::from_field_id(0), price);
+                object
+            })
+            .collect();
+        index.add_all(objects).unwrap();
+        index
+    }
+
+    fn price(value: i64) -> BurkazTerm {
+        BurkazTerm::new(0, &value.into()).unwrap()
+    }
+
+    fn count(index: &BurkazIndex, query: BurkazQuery) -> usize {
+        QueryRunner::new(index.downgrade(), query).count().unwrap()
+    }
+
+    #[test]
+    fn range_query_bounds() {
+        let index = price_index();
+
+        let inclusive = BurkazQuery::Range {
+            lower: Bound::Included(price(10)),
+            upper: Bound::Included(price(50)),
+        };
+        assert_eq!(count(&index, inclusive), 41);
+
+        let exclusive = BurkazQuery::Range {
+            lower: Bound::Excluded(price(10)),
+            upper: Bound::Excluded(price(50)),
+        };
+        assert_eq!(count(&index, exclusive), 39);
+
+        let open_ended = BurkazQuery::Range {
+            lower: Bound::Excluded(price(89)),
+            upper: Bound::Unbounded,
+        };
+        assert_eq!(count(&index, open_ended), 10);
+
+        let unbounded = BurkazQuery::Range {
+            lower: Bound::Unbounded,
+            upper: Bound::Unbounded,
+        };
+        assert_eq!(count(&index, unbounded), 100);
+
+        let mixed_fields = BurkazQuery::Range {
+            lower: Bound::Included(price(10)),
+            upper: Bound::Included(BurkazTerm::new(1, &50i64.into()).unwrap()),
+        };
+        assert!(matches!(
+            mixed_fields.to_tantivy_query(&index),
+            Err(BurkazError::InvalidQuery(_))
+        ));
+    }
+
+    #[test]
+    fn strict_parse_reports_errors() {
+        let index = price_index();
+
+        let syntax_error = BurkazQuery::Parse {
+            query_text: "price:[10 TO".to_owned(),
+            lenient: false,
+            options: None,
+        };
+        assert!(matches!(
+            syntax_error.to_tantivy_query(&index),
+            Err(BurkazError::QueryParse {
+                position: Some(_),
+                ..
+            })
+        ));
+
+        let unknown_field = BurkazQuery::Parse {
+            query_text: "color:red".to_owned(),
+            lenient: false,
+            options: None,
+        };
+        assert!(matches!(
+            unknown_field.to_tantivy_query(&index),
+            Err(BurkazError::QueryParse { position: None, .. })
+        ));
+
+        let valid = BurkazQuery::Parse {
+            query_text: "price:[10 TO 19]".to_owned(),
+            lenient: false,
+            options: None,
+        };
+        assert_eq!(count(&index, valid), 10);
+    }
+
+    #[test]
+    fn lenient_parse_keeps_partial_query() {
+        let index = price_index();
+        let query = BurkazQuery::Parse {
+            query_text: "price:42 color:red".to_owned(),
+            lenient: true,
+            options: None,
+        };
+
+        let (_, errors) = query.to_tantivy_query_lenient(&index).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(count(&index, query), 1);
+    }
+
+    #[test]
+    fn queries_over_f64_date_and_bytes_fields() {
+        let mut schema = BurkazSchema::default();
+        for (name, typ) in [
+            ("rating", BurkazSchemaFieldType::F64),
+            ("published_at", BurkazSchemaFieldType::Date),
+            ("checksum", BurkazSchemaFieldType::Bytes),
+        ] {
+            schema.add_field(BurkazSchemaField::new(
+                name.to_owned(),
+                BurkazSchemaFieldOptions {
+                    fast: true,
+                    date_precision: Some(BurkazDatePrecision::Milliseconds),
+                    ..BurkazSchemaFieldOptions::for_test(typ)
+                },
+            ));
+        }
+        let index =
+            BurkazIndex::new("reviews".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+
+        let date = |millis: i64| DateTime::from_timestamp_millis(millis);
+        let objects = (0..10)
+            .map(|i| {
+                let mut object = TantivyDocument::new();
+                object.add_f64(Field::from_field_id(0), i as f64 / 2.0);
+                object.add_date(Field::from_field_id(1), date(1_000 * i + 250));
+                object.add_bytes(Field::from_field_id(2), &[i as u8, 0xff]);
+                object
+            })
+            .collect();
+        index.add_all(objects).unwrap();
+
+        let rating = |value: f64| BurkazTerm::new(0, &value.into()).unwrap();
+        let ratings = BurkazQuery::Range {
+            lower: Bound::Included(rating(1.5)),
+            upper: Bound::Excluded(rating(3.0)),
+        };
+        assert_eq!(count(&index, ratings), 3);
+
+        let published_at = |millis: i64| BurkazTerm::new(1, &date(millis).into()).unwrap();
+        let same_second = BurkazQuery::Term {
+            term: published_at(3_000),
+            indexing_strategy: Default::default(),
+        };
+        assert_eq!(count(&index, same_second), 1);
+
+        let millis_range = BurkazQuery::Range {
+            lower: Bound::Included(published_at(2_250)),
+            upper: Bound::Included(published_at(4_249)),
+        };
+        assert_eq!(count(&index, millis_range), 2);
+
+        let checksum = BurkazQuery::Term {
+            term: BurkazTerm::new(2, &(&[4u8, 0xff][..]).into()).unwrap(),
+            indexing_strategy: Default::default(),
+        };
+        assert_eq!(count(&index, checksum), 1);
+    }
+
+    #[test]
+    fn json_path_terms() {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "attrs".to_owned(),
+            BurkazSchemaFieldOptions {
+                fieldnorms: true,
+                ..BurkazSchemaFieldOptions::for_test(BurkazSchemaFieldType::Json)
+            },
+        ));
+        let index =
+            BurkazIndex::new("products".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+        let objects = [
+            r#"{"color": "red", "size": {"width": 3}, "in_stock": true}"#,
+            r#"{"color": "blue", "size": {"width": 5}, "in_stock": false}"#,
+            r#"{"color": "red", "size": {"width": 5}}"#,
+        ]
+        .into_iter()
+        .map(|attrs| {
+            let attrs: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(attrs).unwrap();
+            let mut object = TantivyDocument::new();
+            object.add_field_value(Field::from_field_id(0), &OwnedValue::from(attrs));
+            object
+        })
+        .collect();
+        index.add_all(objects).unwrap();
+
+        let attr = |path: &str, value: ReferenceValueLeaf<'_>| BurkazQuery::Term {
+            term: BurkazTerm::new_json(0, path, &value).unwrap(),
+            indexing_strategy: Default::default(),
+        };
+        assert_eq!(count(&index, attr("color", "red".into())), 2);
+        assert_eq!(count(&index, attr("size.width", 5i64.into())), 2);
+        assert_eq!(count(&index, attr("size.width", 3u64.into())), 1);
+        assert_eq!(count(&index, attr("in_stock", true.into())), 1);
+        assert_eq!(count(&index, attr("width", 3i64.into())), 0);
+        assert!(matches!(
+            QueryRunner::new(
+                index.downgrade(),
+                attr("checksum", b"\x01".as_slice().into())
+            )
+            .count(),
+            Err(BurkazError::InvalidField(_))
+        ));
+        assert!(matches!(
+            BurkazTerm::new_json(0, "color", &ReferenceValueLeaf::Null),
+            Err(BurkazError::InvalidField(_))
+        ));
+
+        let parsed = BurkazQuery::Parse {
+            query_text: "attrs.color:blue".to_owned(),
+            lenient: false,
+            options: None,
+        };
+        assert_eq!(count(&index, parsed), 1);
+    }
+
+    #[test]
+    fn negations_match_every_other_object() {
+        let index = price_index();
+        let term = |value: i64| BurkazQuery::Term {
+            term: price(value),
+            indexing_strategy: Default::default(),
+        };
+        let not = |value: i64| BurkazQuery::Not(Box::new(term(value)));
+
+        assert_eq!(count(&index, not(5)), 99);
+        assert_eq!(count(&index, BurkazQuery::And(vec![not(5), not(6)])), 98);
+        assert_eq!(count(&index, BurkazQuery::Or(vec![not(5), not(6)])), 100);
+        assert_eq!(count(&index, BurkazQuery::And(vec![term(5), not(5)])), 0);
+
+        let boolean = |minimum_should_match| BurkazQuery::Boolean {
+            must: vec![],
+            should: vec![term(1), term(2), term(3)],
+            must_not: vec![term(2)],
+            minimum_should_match,
+        };
+        assert_eq!(count(&index, boolean(None)), 2);
+        assert_eq!(count(&index, boolean(Some(2))), 0);
+
+        let must_not_only = BurkazQuery::Boolean {
+            must: vec![],
+            should: vec![],
+            must_not: vec![term(1), term(2)],
+            minimum_should_match: None,
+        };
+        assert_eq!(count(&index, must_not_only), 98);
+    }
+
+    #[test]
+    fn autocomplete_matches_word_prefixes() {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "title".to_owned(),
+            BurkazSchemaFieldOptions {
+                fieldnorms: true,
+                indexing_strategy: Some(BurkazIndexingStrategy::Frequencies),
+                indexing_tokenizer: Some(AUTOCOMPLETE_ANALYZER_NAME.to_owned()),
+                ..BurkazSchemaFieldOptions::for_test(BurkazSchemaFieldType::Text)
+            },
+        ));
+        let index =
+            BurkazIndex::new("products".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+        let titles = ["iPhone 15 Pro", "iPad Air", "Phone case for iPhone"];
+        let objects = titles
+            .iter()
+            .map(|title| {
+                let mut object = TantivyDocument::new();
+                object.add_text(Field::from_field_id(0), title);
+                object
+            })
+            .collect();
+        index.add_all(objects).unwrap();
+
+        let autocomplete = |text: &str| BurkazQuery::Autocomplete {
+            field_id: 0,
+            text: text.to_owned(),
+        };
+        assert_eq!(count(&index, autocomplete("i")), 3);
+        assert_eq!(count(&index, autocomplete("iph")), 2);
+        assert_eq!(count(&index, autocomplete("IPH ca")), 1);
+        assert_eq!(count(&index, autocomplete("ipx")), 0);
+        assert_eq!(count(&index, autocomplete(" ")), 0);
+
+        let runner = QueryRunner::new(index.downgrade(), autocomplete("iph"));
+        let top = runner.search(0, 1).unwrap()[0];
+        let object = index.get(top).unwrap();
+        assert_eq!(
+            object
+                .get_first(Field::from_field_id(0))
+                .and_then(|value| value.as_str()),
+            Some("iPhone 15 Pro")
+        );
+
+        assert!(matches!(
+            autocomplete("1").to_tantivy_query(&price_index()),
+            Err(BurkazError::InvalidField(_))
+        ));
     }
 }
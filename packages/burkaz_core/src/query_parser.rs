@@ -0,0 +1,207 @@
+use tantivy::{
+    Index,
+    query::QueryParser,
+    schema::{Field, FieldType, Schema},
+};
+
+use crate::{error::BurkazError, schema::ID_FIELD_NAME};
+
+/// Fuzzy matching applied to the terms of a field.
+#[derive(Debug, Copy, Clone)]
+pub struct BurkazFuzziness {
+    pub prefix: bool,
+    pub distance: u8,
+    pub transposition_cost_one: bool,
+}
+
+impl Default for BurkazFuzziness {
+    fn default() -> Self {
+        Self {
+            prefix: false,
+            distance: 2,
+            transposition_cost_one: true,
+        }
+    }
+}
+
+/// Options used to build the query parser of `Parse` queries.
+///
+/// By default every field is searched, terms are combined with OR and every
+/// indexed text field uses the default [`BurkazFuzziness`].
+#[derive(Debug, Clone, Default)]
+pub struct BurkazQueryParserOptions {
+    /// Fields searched when the query does not name one. Empty means every field.
+    pub default_fields: Vec<u32>,
+    /// Combines the terms with AND instead of OR.
+    pub conjunction_by_default: bool,
+    pub field_boosts: Vec<(u32, f32)>,
+    /// Per-field fuzziness overrides, `None` turns fuzzy matching off.
+    pub field_fuzziness: Vec<(u32, Option<BurkazFuzziness>)>,
+}
+
+impl BurkazQueryParserOptions {
+    pub fn build(&self, index: &Index) -> crate::Result<QueryParser> {
+        let schema = index.schema();
+
+        let default_fields = if self.default_fields.is_empty() {
+            schema
+                .fields()
+                .filter(|(_, entry)| entry.name() != ID_FIELD_NAME)
+                .map(|(field, _)| field)
+                .collect()
+        } else {
+            self.default_fields
+                .iter()
+                .map(|field_id| field(&schema, *field_id))
+                .collect::<crate::Result<_>>()?
+        };
+
+        let mut parser = QueryParser::for_index(index, default_fields);
+
+        if self.conjunction_by_default {
+            parser.set_conjunction_by_default();
+        }
+
+        for (field_id, boost) in &self.field_boosts {
+            parser.set_field_boost(field(&schema, *field_id)?, *boost);
+        }
+
+        for (field_id, _) in &self.field_fuzziness {
+            field(&schema, *field_id)?;
+        }
+
+        for (field, entry) in schema.fields() {
+            let default_fuzziness = (entry.is_indexed()
+                && matches!(
+                    entry.field_type(),
+                    FieldType::Str(_) | FieldType::JsonObject(_)
+                ))
+            .then(BurkazFuzziness::default);
+
+            let fuzziness = self
+                .field_fuzziness
+                .iter()
+                .rev()
+                .find(|(field_id, _)| *field_id == field.field_id())
+                .map_or(default_fuzziness, |(_, fuzziness)| *fuzziness);
+
+            if let Some(fuzziness) = fuzziness {
+                parser.set_field_fuzzy(
+                    field,
+                    fuzziness.prefix,
+                    fuzziness.distance,
+                    fuzziness.transposition_cost_one,
+                );
+            }
+        }
+
+        Ok(parser)
+    }
+}
+
+fn field(schema: &Schema, field_id: u32) -> crate::Result<Field> {
+    let field = Field::from_field_id(field_id);
+    if schema
+        .fields()
+        .any(|(candidate, entry)| candidate == field && entry.name() != ID_FIELD_NAME)
+    {
+        Ok(field)
+    } else {
+        Err(BurkazError::InvalidField(format!(
+            "field {field_id} does not exist"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::{TantivyDocument, schema::Field};
+
+    use super::*;
+    use crate::{
+        index::{BurkazDirectory, BurkazIndex},
+        query::BurkazQuery,
+        query_runner::QueryRunner,
+        schema::{
+            BurkazSchema, BurkazSchemaField, BurkazSchemaFieldOptions, BurkazSchemaFieldType,
+        },
+    };
+
+    fn index(options: BurkazQueryParserOptions) -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        for name in ["title", "body"] {
+            schema.add_field(BurkazSchemaField::new(
+                name.to_owned(),
+                BurkazSchemaFieldOptions {
+                    stored: false,
+                    fieldnorms: true,
+                    ..BurkazSchemaFieldOptions::for_test(BurkazSchemaFieldType::Text)
+                },
+            ));
+        }
+        schema.set_query_parser_options(options);
+
+        let index =
+            BurkazIndex::new("articles".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+        let objects = [
+            ("apple pie", "sweet"),
+            ("apple", "sour"),
+            ("banana", "apple pie"),
+        ]
+        .into_iter()
+        .map(|(title, body)| {
+            let mut object = TantivyDocument::new();
+            object.add_text(Field::from_field_id(0), title);
+            object.add_text(Field::from_field_id(1), body);
+            object
+        })
+        .collect();
+        index.add_all(objects).unwrap();
+        index
+    }
+
+    fn count(index: &BurkazIndex, query_text: &str) -> usize {
+        let query = BurkazQuery::Parse {
+            query_text: query_text.to_owned(),
+            lenient: false,
+            options: None,
+        };
+        QueryRunner::new(index.downgrade(), query).count().unwrap()
+    }
+
+    #[test]
+    fn default_options_search_every_field_fuzzily() {
+        let index = index(BurkazQueryParserOptions::default());
+
+        assert_eq!(count(&index, "apple pie"), 3);
+        assert_eq!(count(&index, "aple"), 3);
+    }
+
+    #[test]
+    fn custom_options() {
+        let index = index(BurkazQueryParserOptions {
+            default_fields: vec![0],
+            conjunction_by_default: true,
+            field_boosts: vec![(1, 2.0)],
+            field_fuzziness: vec![(0, None)],
+        });
+
+        assert_eq!(count(&index, "apple pie"), 1);
+        assert_eq!(count(&index, "aple"), 0);
+        assert_eq!(count(&index, "body:aple"), 1);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let options = BurkazQueryParserOptions {
+            default_fields: vec![7],
+            ..Default::default()
+        };
+        let index = index(BurkazQueryParserOptions::default());
+
+        assert!(matches!(
+            options.build(index.underlying_index()),
+            Err(BurkazError::InvalidField(_))
+        ));
+    }
+}
@@ -1,19 +1,92 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    ops::Range,
+};
+
 use tantivy::{
-    Searcher,
-    collector::{Count, TopDocs},
+    DocId, Score, Searcher, SegmentReader, TantivyDocument,
+    aggregation::{AggregationCollector, agg_req::Aggregations, agg_result::AggregationResults},
+    collector::{Count, FacetCollector, TopDocs},
     query::Query,
+    schema::{Facet, Field, FieldEntry, FieldType, Schema},
+    snippet::SnippetGenerator,
 };
 
 use crate::{
-    address::BurkazObjectAddr,
+    address::{BurkazObjectAddr, BurkazScoredObjectAddr},
+    aggregation::{
+        BurkazHistogramAggregation, BurkazHistogramAggregationResult, BurkazMetricsAggregation,
+        BurkazMetricsAggregationResult, BurkazTermsAggregation, BurkazTermsAggregationResult,
+    },
+    cursor::{BurkazSearchCursor, SearchPosition},
     error::BurkazError,
-    index::{BurkazIndex, WeakBurkazIndex},
-    query::BurkazQuery,
+    index::{BurkazIndex, WeakBurkazIndex, get_object, object_id},
+    query::{BurkazQuery, BurkazQueryParseError},
+    schema::ID_FIELD_NAME,
+    snapshot::BurkazSnapshot,
 };
 
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum BurkazSortOrder {
+    Ascending = 1,
+    #[default]
+    Descending = 2,
+}
+
+impl BurkazSortOrder {
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(BurkazSortOrder::Ascending),
+            2 => Some(BurkazSortOrder::Descending),
+            _ => None,
+        }
+    }
+
+    /// Maps a fast field value to a key where greater keys come first.
+    #[inline]
+    const fn key(self, value: u64) -> u64 {
+        match self {
+            BurkazSortOrder::Ascending => u64::MAX - value,
+            BurkazSortOrder::Descending => value,
+        }
+    }
+}
+
+/// Number of matching objects filed under a facet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BurkazFacetCount {
+    pub facet: String,
+    pub count: u64,
+}
+
+/// A fragment of a stored text value, with the byte ranges of the fragment that
+/// match the query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BurkazSnippet {
+    pub fragment: String,
+    pub highlighted: Vec<Range<usize>>,
+}
+
+/// A matching object read along with its address.
+#[derive(Debug, Clone)]
+pub struct BurkazHit {
+    pub addr: BurkazScoredObjectAddr,
+    pub object: TantivyDocument,
+}
+
+/// A page of results and the cursor to fetch the next one, `None` when the page
+/// holds fewer objects than asked for.
+#[derive(Debug, Clone)]
+pub struct BurkazSearchPage {
+    pub addrs: Vec<BurkazScoredObjectAddr>,
+    pub cursor: Option<BurkazSearchCursor>,
+}
+
 pub struct QueryRunner {
     _index: WeakBurkazIndex,
     _query: BurkazQuery,
+    _snapshot: Option<BurkazSnapshot>,
 }
 
 impl QueryRunner {
@@ -22,9 +95,17 @@ impl QueryRunner {
         Self {
             _index: index,
             _query: query,
+            _snapshot: None,
         }
     }
 
+    /// Runs every query against the snapshot instead of the latest view of the
+    /// index, so paging stays consistent across commits.
+    pub fn with_snapshot(mut self, snapshot: BurkazSnapshot) -> Self {
+        self._snapshot = Some(snapshot);
+        self
+    }
+
     pub fn into_raw(self) -> *mut Self {
         Box::into_raw(Box::new(self))
     }
@@ -36,37 +117,815 @@ impl QueryRunner {
 
     #[inline]
     fn searcher(&self) -> Option<Searcher> {
-        self._index.upgrade().map(move |index| index.searcher())
+        let index = self._index.upgrade()?;
+        Some(match &self._snapshot {
+            Some(snapshot) => snapshot.searcher().clone(),
+            None => index.searcher(),
+        })
     }
 
-    fn query(&self) -> Option<Box<dyn Query>> {
-        self.index()
-            .map(|index| self._query.to_tantivy_query(&index))
+    fn query(&self) -> crate::Result<Box<dyn Query>> {
+        self._query
+            .to_tantivy_query(&self.index().ok_or(BurkazError::IndexClosed)?)
+    }
+
+    /// Returns the errors found while parsing the lenient `Parse` queries.
+    pub fn parse_errors(&self) -> crate::Result<Vec<BurkazQueryParseError>> {
+        let index = self.index().ok_or(BurkazError::IndexClosed)?;
+        let (_, errors) = self._query.to_tantivy_query_lenient(&index)?;
+        Ok(errors)
     }
 
     pub fn count(&self) -> crate::Result<usize> {
         self.searcher()
             .ok_or(BurkazError::IndexClosed)?
-            .search(&self.query().ok_or(BurkazError::IndexClosed)?, &Count)
+            .search(&self.query()?, &Count)
             .map_err(Into::into)
     }
 
     pub fn search(&self, offset: usize, limit: usize) -> crate::Result<Vec<BurkazObjectAddr>> {
-        let query = self.query().ok_or(BurkazError::IndexClosed)?;
+        Ok(self
+            .search_scored(offset, limit)?
+            .into_iter()
+            .map(move |scored_addr| scored_addr.addr)
+            .collect())
+    }
+
+    pub fn search_scored(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> crate::Result<Vec<BurkazScoredObjectAddr>> {
+        let query = self.query()?;
         let collector = TopDocs::with_limit(limit).and_offset(offset);
         let score_and_addrs = self
             .searcher()
             .ok_or(BurkazError::IndexClosed)?
             .search(&query, &collector)?;
-        Ok(score_and_addrs
-            .iter()
-            .map(move |(_, addr)| (*addr).into())
+        Ok(score_and_addrs.into_iter().map(Into::into).collect())
+    }
+
+    /// Runs the query and reads the matching objects in the same pass, most
+    /// relevant first. Only the stored values of `field_ids` are kept, an empty
+    /// list keeps every stored field.
+    pub fn search_objects(
+        &self,
+        offset: usize,
+        limit: usize,
+        field_ids: &[u32],
+    ) -> crate::Result<Vec<BurkazHit>> {
+        let searcher = self.searcher().ok_or(BurkazError::IndexClosed)?;
+        let mut fields = Vec::with_capacity(field_ids.len());
+        for field_id in field_ids {
+            let entry = field_entry(searcher.schema(), *field_id)?;
+            if !entry.is_stored() {
+                return Err(BurkazError::InvalidField(format!(
+                    "cannot read field `{}`, it is not stored",
+                    entry.name()
+                )));
+            }
+            fields.push(Field::from_field_id(*field_id));
+        }
+        // Tantivy cannot collect zero objects.
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let collector = TopDocs::with_limit(limit).and_offset(offset);
+        searcher
+            .search(&self.query()?, &collector)?
+            .into_iter()
+            .map(|scored_addr| {
+                let object = searcher.doc::<TantivyDocument>(scored_addr.1)?;
+                let object = if fields.is_empty() {
+                    object
+                } else {
+                    let mut projected = TantivyDocument::new();
+                    for (field, value) in object.field_values() {
+                        if fields.contains(&field) {
+                            projected.add_field_value(field, value);
+                        }
+                    }
+                    projected
+                };
+                Ok(BurkazHit {
+                    addr: scored_addr.into(),
+                    object,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the stable ids of the matching objects, most relevant first.
+    pub fn search_ids(&self, offset: usize, limit: usize) -> crate::Result<Vec<u64>> {
+        let searcher = self.searcher().ok_or(BurkazError::IndexClosed)?;
+        // Tantivy cannot collect zero objects.
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let collector = TopDocs::with_limit(limit).and_offset(offset);
+        searcher
+            .search(&self.query()?, &collector)?
+            .into_iter()
+            .map(|(_, addr)| object_id(&searcher, addr))
+            .collect()
+    }
+
+    /// Orders the results by a numeric, date or boolean fast field, breaking ties by
+    /// score. Objects without a value for the field come last.
+    pub fn search_sorted(
+        &self,
+        field_id: u32,
+        order: BurkazSortOrder,
+        offset: usize,
+        limit: usize,
+    ) -> crate::Result<Vec<BurkazObjectAddr>> {
+        let searcher = self.searcher().ok_or(BurkazError::IndexClosed)?;
+        let field_name = sort_field_name(&searcher, field_id)?;
+        // Tantivy cannot collect zero objects.
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let query = self.query()?;
+
+        // `order_by_fast_field` disables scoring, so the fast field value is
+        // folded into a tweaked score to keep the relevance as a tie-break.
+        let collector = TopDocs::with_limit(limit).and_offset(offset).tweak_score(
+            move |segment_reader: &SegmentReader| {
+                let column = segment_reader
+                    .fast_fields()
+                    .u64_lenient(&field_name)
+                    .ok()
+                    .flatten()
+                    .map(|(column, _)| column);
+
+                move |doc: DocId, score: Score| {
+                    let value = column.as_ref().and_then(|column| column.first(doc));
+                    (value.map(|value| order.key(value)), score)
+                }
+            },
+        );
+
+        let sorted_addrs = searcher.search(&query, &collector)?;
+        Ok(sorted_addrs
+            .into_iter()
+            .map(move |(_, addr)| addr.into())
             .collect())
     }
 
+    /// Returns the page of matching objects following `cursor`, or the first page
+    /// without one. Objects are ordered by relevance, or by a fast field like in
+    /// [`QueryRunner::search_sorted`] when `sort` is given.
+    ///
+    /// Skipped pages are not collected again, so deep pages cost as much as the
+    /// first one. The cursor must come from a search with the same `sort`.
+    pub fn search_after(
+        &self,
+        sort: Option<(u32, BurkazSortOrder)>,
+        cursor: Option<&BurkazSearchCursor>,
+        limit: usize,
+    ) -> crate::Result<BurkazSearchPage> {
+        if cursor.is_some_and(|cursor| cursor.sort != sort) {
+            return Err(BurkazError::InvalidCursor(
+                "cursor comes from a search with another order".to_owned(),
+            ));
+        }
+
+        let searcher = self.searcher().ok_or(BurkazError::IndexClosed)?;
+        let field_name = sort
+            .map(|(field_id, _)| sort_field_name(&searcher, field_id))
+            .transpose()?;
+        // Tantivy cannot collect zero objects, and an empty page ends the search.
+        if limit == 0 {
+            return Ok(BurkazSearchPage {
+                addrs: Vec::new(),
+                cursor: None,
+            });
+        }
+        let order = sort.map(|(_, order)| order).unwrap_or_default();
+        let after = cursor.map(BurkazSearchCursor::position);
+        let segment_ids = searcher
+            .segment_readers()
+            .iter()
+            .map(SegmentReader::segment_id)
+            .collect::<Vec<_>>();
+
+        // Objects up to the cursor get no position, which ranks them below every
+        // object left to return.
+        let collector =
+            TopDocs::with_limit(limit).tweak_score(move |segment_reader: &SegmentReader| {
+                let segment_ord = segment_ids
+                    .iter()
+                    .position(|segment_id| *segment_id == segment_reader.segment_id())
+                    .unwrap_or_default() as u64;
+                let column = field_name
+                    .as_ref()
+                    .and_then(|field_name| {
+                        segment_reader
+                            .fast_fields()
+                            .u64_lenient(field_name)
+                            .ok()
+                            .flatten()
+                    })
+                    .map(|(column, _)| column);
+
+                move |doc: DocId, score: Score| -> Option<SearchPosition> {
+                    let value = column.as_ref().and_then(|column| column.first(doc));
+                    let position = (
+                        value.map(|value| order.key(value)),
+                        score,
+                        Reverse(segment_ord << 32 | doc as u64),
+                    );
+                    match after {
+                        Some(after) if position.partial_cmp(&after) != Some(Ordering::Less) => None,
+                        _ => Some(position),
+                    }
+                }
+            });
+
+        let positions = searcher
+            .search(&self.query()?, &collector)?
+            .into_iter()
+            .filter_map(|(position, addr)| Some((position?, BurkazObjectAddr::from(addr))))
+            .collect::<Vec<_>>();
+
+        // A short page is the last one, there is nothing after it.
+        let cursor = positions.last().filter(|_| positions.len() == limit).map(
+            |((sort_key, score, _), addr)| BurkazSearchCursor {
+                sort,
+                sort_key: *sort_key,
+                score: *score,
+                addr: *addr,
+            },
+        );
+        let addrs = positions
+            .into_iter()
+            .map(|((_, score, _), addr)| BurkazScoredObjectAddr { addr, score })
+            .collect();
+
+        Ok(BurkazSearchPage { addrs, cursor })
+    }
+
+    /// Counts the matching objects under each direct child of the root facets,
+    /// keeping the `top_k` most frequent children of every root. A `top_k` of
+    /// zero keeps every child.
+    ///
+    /// Children are returned root by root, as full paths such as
+    /// `/electronics/phones`.
+    pub fn facet_counts(
+        &self,
+        field_id: u32,
+        root_paths: &[&str],
+        top_k: usize,
+    ) -> crate::Result<Vec<BurkazFacetCount>> {
+        let searcher = self.searcher().ok_or(BurkazError::IndexClosed)?;
+        let entry = field_entry(searcher.schema(), field_id)?;
+        if !matches!(entry.field_type(), FieldType::Facet(_)) {
+            return Err(BurkazError::InvalidField(format!(
+                "cannot count facets of field `{}`, it is not a facet field",
+                entry.name()
+            )));
+        }
+
+        let mut roots = Vec::<Facet>::with_capacity(root_paths.len());
+        for root_path in root_paths {
+            let root = Facet::from_text(root_path)
+                .map_err(|error| BurkazError::InvalidFacet(error.to_string()))?;
+            if roots.contains(&root) {
+                continue;
+            }
+            // The facet collector only counts disjoint subtrees.
+            if let Some(other) = roots
+                .iter()
+                .find(|other| other.is_prefix_of(&root) || root.is_prefix_of(other))
+            {
+                return Err(BurkazError::InvalidFacet(format!(
+                    "`{root}` and `{other}` overlap, count them separately"
+                )));
+            }
+            roots.push(root);
+        }
+
+        let mut collector = FacetCollector::for_field(entry.name());
+        for root in &roots {
+            collector.add_facet(root.clone());
+        }
+
+        let facet_counts = searcher.search(&self.query()?, &collector)?;
+
+        let mut counts = Vec::new();
+        for root in roots {
+            let children: Vec<(&Facet, u64)> = if top_k == 0 {
+                facet_counts.get(root).collect()
+            } else {
+                facet_counts.top_k(root, top_k)
+            };
+            counts.extend(children.into_iter().map(|(facet, count)| BurkazFacetCount {
+                facet: facet.to_string(),
+                count,
+            }));
+        }
+        Ok(counts)
+    }
+
+    /// Builds a snippet of a stored text field for every address, highlighting the
+    /// terms of the query. Fragments are at most `max_num_chars` long, and empty
+    /// when the object has no matching text.
+    pub fn snippets(
+        &self,
+        field_id: u32,
+        addrs: &[BurkazObjectAddr],
+        max_num_chars: usize,
+    ) -> crate::Result<Vec<BurkazSnippet>> {
+        let searcher = self.searcher().ok_or(BurkazError::IndexClosed)?;
+        let entry = field_entry(searcher.schema(), field_id)?;
+        if !matches!(entry.field_type(), FieldType::Str(_)) {
+            return Err(BurkazError::InvalidField(format!(
+                "cannot build snippets of field `{}`, only text fields are supported",
+                entry.name()
+            )));
+        }
+
+        if !entry.is_stored() || !entry.is_indexed() {
+            return Err(BurkazError::InvalidField(format!(
+                "cannot build snippets of field `{}`, it must be stored and indexed",
+                entry.name()
+            )));
+        }
+
+        let mut generator =
+            SnippetGenerator::create(&searcher, &self.query()?, Field::from_field_id(field_id))?;
+        generator.set_max_num_chars(max_num_chars);
+
+        addrs
+            .iter()
+            .map(|addr| {
+                let snippet = generator.snippet_from_doc(&get_object(&searcher, *addr)?);
+                Ok(BurkazSnippet {
+                    fragment: snippet.fragment().to_owned(),
+                    highlighted: snippet.highlighted().to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Buckets the matching objects by the values of a fast field.
+    pub fn terms_aggregation(
+        &self,
+        request: &BurkazTermsAggregation,
+    ) -> crate::Result<BurkazTermsAggregationResult> {
+        self.aggregate(
+            |schema, aggregations| request.to_tantivy_aggregations(schema, "", aggregations),
+            |schema, results| {
+                BurkazTermsAggregationResult::from_tantivy_results(request, schema, "", results)
+            },
+        )
+    }
+
+    /// Computes summary statistics of a numeric fast field over the matching objects.
+    pub fn metrics_aggregation(
+        &self,
+        request: &BurkazMetricsAggregation,
+    ) -> crate::Result<BurkazMetricsAggregationResult> {
+        self.aggregate(
+            |schema, aggregations| request.to_tantivy_aggregations(schema, "", aggregations),
+            |_, results| BurkazMetricsAggregationResult::from_tantivy_results(request, "", results),
+        )
+    }
+
+    /// Buckets the matching objects by fixed size ranges of a numeric or date
+    /// fast field, computing the sub-aggregations over every bucket.
+    pub fn histogram_aggregation(
+        &self,
+        request: &BurkazHistogramAggregation,
+    ) -> crate::Result<BurkazHistogramAggregationResult> {
+        self.aggregate(
+            |schema, aggregations| request.to_tantivy_aggregations(schema, "", aggregations),
+            |schema, results| {
+                BurkazHistogramAggregationResult::from_tantivy_results(request, schema, "", results)
+            },
+        )
+    }
+
+    fn aggregate<R>(
+        &self,
+        build: impl FnOnce(&Schema, &mut Aggregations) -> crate::Result<()>,
+        read: impl FnOnce(&Schema, &mut AggregationResults) -> crate::Result<R>,
+    ) -> crate::Result<R> {
+        let searcher = self.searcher().ok_or(BurkazError::IndexClosed)?;
+        let schema = searcher.schema();
+
+        let mut aggregations = Aggregations::new();
+        build(schema, &mut aggregations)?;
+
+        let collector = AggregationCollector::from_aggs(aggregations, Default::default());
+        let mut results = searcher.search(&self.query()?, &collector)?;
+        read(schema, &mut results)
+    }
+
     pub fn delete_all(&self) -> crate::Result<()> {
         self.index()
             .ok_or(BurkazError::IndexClosed)?
-            .delete_all_by_query(self.query().ok_or(BurkazError::IndexClosed)?)
+            .delete_all_by_query(self.query()?)
+    }
+}
+
+pub(crate) fn field_entry(schema: &Schema, field_id: u32) -> crate::Result<FieldEntry> {
+    let field = Field::from_field_id(field_id);
+    schema
+        .fields()
+        .find(|(candidate, entry)| *candidate == field && entry.name() != ID_FIELD_NAME)
+        .map(|(_, entry)| entry.clone())
+        .ok_or_else(|| BurkazError::InvalidField(format!("field {field_id} does not exist")))
+}
+
+fn sort_field_name(searcher: &Searcher, field_id: u32) -> crate::Result<String> {
+    let entry = field_entry(searcher.schema(), field_id)?;
+
+    if !matches!(
+        entry.field_type(),
+        FieldType::I64(_)
+            | FieldType::Bool(_)
+            | FieldType::F64(_)
+            | FieldType::U64(_)
+            | FieldType::Date(_)
+    ) {
+        return Err(BurkazError::InvalidField(format!(
+            "cannot sort by field `{}`, only numeric, date and boolean fields are sortable",
+            entry.name()
+        )));
+    }
+
+    if !entry.is_fast() {
+        return Err(BurkazError::InvalidField(format!(
+            "cannot sort by field `{}`, it is not declared as fast",
+            entry.name()
+        )));
+    }
+
+    Ok(entry.name().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::TantivyDocument;
+
+    use super::*;
+    use crate::{
+        index::BurkazDirectory,
+        schema::{
+            BurkazSchema, BurkazSchemaField, BurkazSchemaFieldOptions, BurkazSchemaFieldType,
+        },
+        term::BurkazTerm,
+    };
+
+    fn field_options(typ: BurkazSchemaFieldType, fast: bool) -> BurkazSchemaFieldOptions {
+        BurkazSchemaFieldOptions {
+            fieldnorms: true,
+            fast,
+            ..BurkazSchemaFieldOptions::for_test(typ)
+        }
+    }
+
+    fn index() -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "title".to_owned(),
+            field_options(BurkazSchemaFieldType::Text, false),
+        ));
+        schema.add_field(BurkazSchemaField::new(
+            "created_at".to_owned(),
+            field_options(BurkazSchemaFieldType::Int64, true),
+        ));
+        schema.add_field(BurkazSchemaField::new(
+            "views".to_owned(),
+            field_options(BurkazSchemaFieldType::Int64, false),
+        ));
+        let index =
+            BurkazIndex::new("posts".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+
+        let objects = [
+            ("rust and more", Some(20)),
+            ("rust", Some(20)),
+            ("rust", None),
+            ("rust", Some(30)),
+            ("rust", Some(10)),
+        ]
+        .into_iter()
+        .map(|(title, created_at)| {
+            let mut object = TantivyDocument::new();
+            object.add_text(Field::from_field_id(0), title);
+            if let Some(created_at) = created_at {
+                object.add_i64(Field::from_field_id(1), created_at);
+            }
+            object
+        })
+        .collect();
+        index.add_all(objects).unwrap();
+        index
+    }
+
+    fn sorted(runner: &QueryRunner, order: BurkazSortOrder) -> Vec<(Option<i64>, String)> {
+        let index = runner.index().unwrap();
+        runner
+            .search_sorted(1, order, 0, 10)
+            .unwrap()
+            .into_iter()
+            .map(|addr| {
+                let object = index.get(addr).unwrap();
+                let created_at = object
+                    .get_first(Field::from_field_id(1))
+                    .and_then(|value| tantivy::schema::Value::as_i64(&value));
+                let title = object
+                    .get_first(Field::from_field_id(0))
+                    .and_then(|value| tantivy::schema::Value::as_str(&value).map(str::to_owned))
+                    .unwrap();
+                (created_at, title)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn search_scored_returns_descending_scores() {
+        let index = index();
+        let query = BurkazQuery::Term {
+            term: BurkazTerm::new(0, &"rust".into()).unwrap(),
+            indexing_strategy: Default::default(),
+        };
+        let runner = QueryRunner::new(index.downgrade(), query);
+
+        let all = runner.search_scored(0, 10).unwrap();
+        assert_eq!(all.len(), 5);
+        assert!(all.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        assert!(all.iter().all(|hit| hit.score > 0.0));
+        // The longer title dilutes the term and ranks last.
+        let last = index.get(all[4].addr).unwrap();
+        assert_eq!(
+            last.get_first(Field::from_field_id(0))
+                .and_then(|value| tantivy::schema::Value::as_str(&value).map(str::to_owned)),
+            Some("rust and more".to_owned())
+        );
+
+        let addrs = |hits: Vec<BurkazScoredObjectAddr>| {
+            hits.into_iter().map(|hit| hit.addr).collect::<Vec<_>>()
+        };
+        let page = addrs(runner.search_scored(1, 2).unwrap());
+        assert_eq!(page, addrs(all[1..3].to_vec()));
+        assert!(runner.search_scored(5, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn search_sorted_by_fast_field() {
+        let index = index();
+        let query = BurkazQuery::Term {
+            term: BurkazTerm::new(0, &"rust".into()).unwrap(),
+            indexing_strategy: Default::default(),
+        };
+        let runner = QueryRunner::new(index.downgrade(), query);
+
+        let created_at = |result: Vec<(Option<i64>, String)>| {
+            result
+                .into_iter()
+                .map(|(created_at, _)| created_at)
+                .collect::<Vec<_>>()
+        };
+
+        let descending = sorted(&runner, BurkazSortOrder::Descending);
+        assert_eq!(
+            created_at(descending.clone()),
+            vec![Some(30), Some(20), Some(20), Some(10), None]
+        );
+        // Ties are broken by score, shorter titles score higher.
+        assert_eq!(descending[1].1, "rust");
+
+        let ascending = sorted(&runner, BurkazSortOrder::Ascending);
+        assert_eq!(
+            created_at(ascending.clone()),
+            vec![Some(10), Some(20), Some(20), Some(30), None]
+        );
+        assert_eq!(ascending[1].1, "rust");
+        assert!(
+            runner
+                .search_sorted(1, BurkazSortOrder::Ascending, 0, 0)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn search_sorted_requires_fast_field() {
+        let index = index();
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+
+        for field_id in [0, 2, 3] {
+            assert!(matches!(
+                runner.search_sorted(field_id, BurkazSortOrder::Descending, 0, 10),
+                Err(BurkazError::InvalidField(_))
+            ));
+        }
+    }
+
+    fn search_all_after(
+        runner: &QueryRunner,
+        sort: Option<(u32, BurkazSortOrder)>,
+    ) -> Vec<BurkazScoredObjectAddr> {
+        let mut addrs = Vec::new();
+        // Cursors go through their byte encoding, as they do over FFI.
+        let mut cursor_bytes: Option<Vec<u8>> = None;
+        loop {
+            let cursor = cursor_bytes
+                .as_deref()
+                .map(|bytes| BurkazSearchCursor::from_bytes(bytes).unwrap());
+            let page = runner.search_after(sort, cursor.as_ref(), 2).unwrap();
+            assert_eq!(page.cursor.is_some(), page.addrs.len() == 2);
+            addrs.extend(page.addrs);
+            match page.cursor {
+                Some(next) => cursor_bytes = Some(next.to_bytes()),
+                None => return addrs,
+            }
+        }
+    }
+
+    #[test]
+    fn search_after_pages_through_every_object() {
+        let index = index();
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+
+        let scored = search_all_after(&runner, None);
+        let mut addrs = scored
+            .iter()
+            .map(|scored_addr| scored_addr.addr.val())
+            .collect::<Vec<_>>();
+        assert_eq!(addrs.len(), 5);
+        addrs.sort();
+        addrs.dedup();
+        assert_eq!(addrs.len(), 5);
+
+        let sort = Some((1, BurkazSortOrder::Descending));
+        let created_at = search_all_after(&runner, sort)
+            .into_iter()
+            .map(|scored_addr| {
+                index
+                    .get(scored_addr.addr)
+                    .unwrap()
+                    .get_first(Field::from_field_id(1))
+                    .and_then(|value| tantivy::schema::Value::as_i64(&value))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            created_at,
+            vec![Some(30), Some(20), Some(20), Some(10), None]
+        );
+
+        let empty = runner.search_after(sort, None, 0).unwrap();
+        assert!(empty.addrs.is_empty() && empty.cursor.is_none());
+
+        let cursor = runner.search_after(None, None, 1).unwrap().cursor.unwrap();
+        assert!(matches!(
+            runner.search_after(sort, Some(&cursor), 1),
+            Err(BurkazError::InvalidCursor(_))
+        ));
+    }
+
+    #[test]
+    fn search_objects_projects_fields() {
+        let index = index();
+        let query = BurkazQuery::Term {
+            term: BurkazTerm::new(0, &"more".into()).unwrap(),
+            indexing_strategy: Default::default(),
+        };
+        let runner = QueryRunner::new(index.downgrade(), query);
+
+        let hits = runner.search_objects(0, 10, &[1]).unwrap();
+        assert_eq!(hits.len(), 1);
+        let values = hits[0].object.field_values().collect::<Vec<_>>();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].0, Field::from_field_id(1));
+        assert_eq!(tantivy::schema::Value::as_i64(&values[0].1), Some(20));
+
+        let hits = runner.search_objects(0, 10, &[]).unwrap();
+        assert_eq!(hits[0].object.field_values().count(), 2);
+        assert!(runner.search_objects(0, 0, &[1]).unwrap().is_empty());
+
+        assert!(matches!(
+            runner.search_objects(0, 10, &[7]),
+            Err(BurkazError::InvalidField(_))
+        ));
+    }
+
+    fn catalog_index() -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "category".to_owned(),
+            field_options(BurkazSchemaFieldType::Facet, false),
+        ));
+        let index =
+            BurkazIndex::new("catalog".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+
+        let objects = [
+            "/electronics/phones/android",
+            "/electronics/phones/ios",
+            "/electronics/phones/android",
+            "/electronics/laptops",
+            "/books/fiction",
+        ]
+        .into_iter()
+        .map(|category| {
+            let mut object = TantivyDocument::new();
+            object.add_facet(Field::from_field_id(0), category);
+            object
+        })
+        .collect();
+        index.add_all(objects).unwrap();
+        index
+    }
+
+    #[test]
+    fn facet_counts_per_child() {
+        let index = catalog_index();
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+
+        let counts = |root_paths: &[&str], top_k: usize| {
+            runner
+                .facet_counts(0, root_paths, top_k)
+                .unwrap()
+                .into_iter()
+                .map(|count| (count.facet, count.count))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            counts(&["/"], 0),
+            vec![("/books".to_owned(), 1), ("/electronics".to_owned(), 4)]
+        );
+        assert_eq!(
+            counts(&["/electronics/phones", "/books"], 1),
+            vec![
+                ("/electronics/phones/android".to_owned(), 2),
+                ("/books/fiction".to_owned(), 1),
+            ]
+        );
+
+        let phones = BurkazQuery::Term {
+            term: BurkazTerm::new(
+                0,
+                &tantivy::schema::document::ReferenceValueLeaf::Facet(
+                    Facet::from("/electronics/phones").encoded_str(),
+                ),
+            )
+            .unwrap(),
+            indexing_strategy: Default::default(),
+        };
+        let runner = QueryRunner::new(index.downgrade(), phones);
+        assert_eq!(runner.count().unwrap(), 3);
+        assert_eq!(
+            runner.facet_counts(0, &["/electronics"], 0).unwrap(),
+            vec![BurkazFacetCount {
+                facet: "/electronics/phones".to_owned(),
+                count: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn facet_counts_rejects_invalid_roots() {
+        let index = catalog_index();
+        let runner = QueryRunner::new(index.downgrade(), BurkazQuery::All);
+
+        assert!(matches!(
+            runner.facet_counts(0, &["/electronics", "/electronics/phones"], 0),
+            Err(BurkazError::InvalidFacet(_))
+        ));
+        assert!(matches!(
+            runner.facet_counts(0, &["electronics"], 0),
+            Err(BurkazError::InvalidFacet(_))
+        ));
+    }
+
+    #[test]
+    fn snippets_highlight_query_terms() {
+        let index = index();
+        let query = BurkazQuery::Term {
+            term: BurkazTerm::new(0, &"rust".into()).unwrap(),
+            indexing_strategy: Default::default(),
+        };
+        let runner = QueryRunner::new(index.downgrade(), query);
+        let addrs = runner.search(0, 10).unwrap();
+
+        let snippets = runner.snippets(0, &addrs, 150).unwrap();
+        assert_eq!(snippets.len(), 5);
+        let snippet = snippets
+            .iter()
+            .find(|snippet| snippet.fragment == "rust and more")
+            .unwrap();
+        assert_eq!(snippet.highlighted, vec![0..4]);
+
+        let shortened = runner.snippets(0, &addrs[..1], 4).unwrap();
+        assert_eq!(shortened[0].fragment, "rust");
+
+        assert!(matches!(
+            runner.snippets(1, &addrs, 150),
+            Err(BurkazError::InvalidField(_))
+        ));
     }
 }
@@ -1,10 +1,16 @@
 #![allow(unused)]
 
 use tantivy::schema::{
-    FieldEntry, FieldType, IndexRecordOption, NumericOptions, Schema, TextFieldIndexing,
-    TextOptions,
+    BytesOptions, DateOptions, DateTimePrecision, FacetOptions, FieldEntry, FieldType,
+    IndexRecordOption, JsonObjectOptions, NumericOptions, Schema, TextFieldIndexing, TextOptions,
 };
 
+use crate::{analyzer::BurkazAnalyzer, error::BurkazError, query_parser::BurkazQueryParserOptions};
+
+/// Name of the internal field holding the stable id of every object. It is
+/// added after the fields of the schema, so it never shifts their ids.
+pub const ID_FIELD_NAME: &str = "_burkaz_id";
+
 #[derive(Debug)]
 pub struct BurkazSchemaField {
     pub name: String,
@@ -22,6 +28,12 @@ pub enum BurkazSchemaFieldType {
     Int64,
     Text,
     Boolean,
+    F64,
+    U64,
+    Date,
+    Bytes,
+    Json,
+    Facet,
 }
 
 #[derive(Debug)]
@@ -32,11 +44,35 @@ pub struct BurkazSchemaFieldOptions {
     pub indexed: bool,
     pub fieldnorms: bool,
     pub fast: bool,
+    /// Marks the field as the unique key used by upserts and deletes by key.
+    pub unique: bool,
     pub indexing_strategy: Option<BurkazIndexingStrategy>,
+    /// Precision of the fast values of a date field.
+    pub date_precision: Option<BurkazDatePrecision>,
     pub fast_tokenizer: Option<String>,
     pub indexing_tokenizer: Option<String>,
 }
 
+#[cfg(test)]
+impl BurkazSchemaFieldOptions {
+    /// Stored and indexed options of the given type, tests override the rest.
+    pub fn for_test(typ: BurkazSchemaFieldType) -> Self {
+        Self {
+            typ,
+            stored: true,
+            coerce: false,
+            indexed: true,
+            fieldnorms: false,
+            fast: false,
+            unique: false,
+            indexing_strategy: None,
+            date_precision: None,
+            fast_tokenizer: None,
+            indexing_tokenizer: None,
+        }
+    }
+}
+
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Default)]
 pub enum BurkazIndexingStrategy {
@@ -73,9 +109,48 @@ impl Into<IndexRecordOption> for BurkazIndexingStrategy {
     }
 }
 
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Default)]
+pub enum BurkazDatePrecision {
+    #[default]
+    Seconds = 1,
+    Milliseconds = 2,
+    Microseconds = 3,
+    Nanoseconds = 4,
+}
+
+impl BurkazDatePrecision {
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(BurkazDatePrecision::Seconds),
+            2 => Some(BurkazDatePrecision::Milliseconds),
+            3 => Some(BurkazDatePrecision::Microseconds),
+            4 => Some(BurkazDatePrecision::Nanoseconds),
+            _ => None,
+        }
+    }
+
+    pub const fn to_code(self) -> u8 {
+        self as u8
+    }
+}
+
+impl From<BurkazDatePrecision> for DateTimePrecision {
+    fn from(precision: BurkazDatePrecision) -> Self {
+        match precision {
+            BurkazDatePrecision::Seconds => DateTimePrecision::Seconds,
+            BurkazDatePrecision::Milliseconds => DateTimePrecision::Milliseconds,
+            BurkazDatePrecision::Microseconds => DateTimePrecision::Microseconds,
+            BurkazDatePrecision::Nanoseconds => DateTimePrecision::Nanoseconds,
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct BurkazSchema {
     fields: Vec<BurkazSchemaField>,
+    query_parser_options: BurkazQueryParserOptions,
+    analyzers: Vec<BurkazAnalyzer>,
 }
 
 impl BurkazSchema {
@@ -83,6 +158,24 @@ impl BurkazSchema {
         self.fields.push(field);
     }
 
+    /// Adds an analyzer registered on the index when it is opened, fields use
+    /// it by setting its name as their tokenizer.
+    pub fn add_analyzer(&mut self, analyzer: BurkazAnalyzer) {
+        self.analyzers.push(analyzer);
+    }
+
+    pub fn analyzers(&self) -> &[BurkazAnalyzer] {
+        &self.analyzers
+    }
+
+    pub fn set_query_parser_options(&mut self, options: BurkazQueryParserOptions) {
+        self.query_parser_options = options;
+    }
+
+    pub fn query_parser_options(&self) -> &BurkazQueryParserOptions {
+        &self.query_parser_options
+    }
+
     pub fn into_raw(self) -> *mut Self {
         Box::into_raw(Box::new(self))
     }
@@ -90,6 +183,61 @@ impl BurkazSchema {
     pub unsafe fn from_raw(ptr: *mut Self) -> Self {
         unsafe { *Box::from_raw(ptr) }
     }
+
+    /// Returns the id of the field marked as unique, if any.
+    ///
+    /// Fails when more than one field is marked as unique, when the unique
+    /// field is not indexed or when a unique text field is split into tokens,
+    /// keys are matched as a whole so they must use the `raw` tokenizer.
+    pub fn key_field(&self) -> crate::Result<Option<u32>> {
+        let mut key_fields = self
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.options.unique);
+
+        let Some((field_id, field)) = key_fields.next() else {
+            return Ok(None);
+        };
+
+        if key_fields.next().is_some() {
+            return Err(BurkazError::InvalidSchema(
+                "only one field can be marked as unique".to_owned(),
+            ));
+        }
+
+        if !field.options.indexed {
+            return Err(BurkazError::InvalidSchema(format!(
+                "unique field `{}` must be indexed",
+                field.name
+            )));
+        }
+
+        if let BurkazSchemaFieldType::Text = field.options.typ
+            && field
+                .options
+                .indexing_tokenizer
+                .as_deref()
+                .is_some_and(|tokenizer| tokenizer != "raw")
+        {
+            return Err(BurkazError::InvalidSchema(format!(
+                "unique text field `{}` must use the `raw` tokenizer",
+                field.name
+            )));
+        }
+
+        Ok(Some(field_id as u32))
+    }
+
+    /// Fails when a field uses the name reserved for the internal id field.
+    pub fn validate_field_names(&self) -> crate::Result<()> {
+        if self.fields.iter().any(|field| field.name == ID_FIELD_NAME) {
+            return Err(BurkazError::InvalidSchema(format!(
+                "field name `{ID_FIELD_NAME}` is reserved"
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl Into<Schema> for BurkazSchema {
@@ -98,6 +246,10 @@ impl Into<Schema> for BurkazSchema {
         for field in self.fields {
             schema.add_field(FieldEntry::new(field.name, field.options.into()));
         }
+        schema.add_u64_field(
+            ID_FIELD_NAME,
+            NumericOptions::default().set_indexed().set_fast(),
+        );
         schema.build()
     }
 }
@@ -108,6 +260,12 @@ impl Into<FieldType> for BurkazSchemaFieldOptions {
             BurkazSchemaFieldType::Int64 => FieldType::I64(self.into()),
             BurkazSchemaFieldType::Text => FieldType::Str(self.into()),
             BurkazSchemaFieldType::Boolean => FieldType::Bool(self.into()),
+            BurkazSchemaFieldType::F64 => FieldType::F64(self.into()),
+            BurkazSchemaFieldType::U64 => FieldType::U64(self.into()),
+            BurkazSchemaFieldType::Date => FieldType::Date(self.into()),
+            BurkazSchemaFieldType::Bytes => FieldType::Bytes(self.into()),
+            BurkazSchemaFieldType::Json => FieldType::JsonObject(self.into()),
+            BurkazSchemaFieldType::Facet => FieldType::Facet(self.into()),
         }
     }
 }
@@ -140,6 +298,71 @@ impl Into<NumericOptions> for BurkazSchemaFieldOptions {
     }
 }
 
+impl From<BurkazSchemaFieldOptions> for DateOptions {
+    fn from(field_options: BurkazSchemaFieldOptions) -> Self {
+        let mut options = DateOptions::default();
+
+        if field_options.indexed {
+            options = options.set_indexed();
+        }
+
+        if field_options.fast {
+            options = options.set_fast();
+        }
+
+        if field_options.stored {
+            options = options.set_stored();
+        }
+
+        if field_options.fieldnorms {
+            options = options.set_fieldnorm();
+        }
+
+        if let Some(date_precision) = field_options.date_precision {
+            options = options.set_precision(date_precision.into());
+        }
+
+        options
+    }
+}
+
+impl From<BurkazSchemaFieldOptions> for BytesOptions {
+    fn from(field_options: BurkazSchemaFieldOptions) -> Self {
+        let mut options = BytesOptions::default();
+
+        if field_options.indexed {
+            options = options.set_indexed();
+        }
+
+        if field_options.fast {
+            options = options.set_fast();
+        }
+
+        if field_options.stored {
+            options = options.set_stored();
+        }
+
+        if field_options.fieldnorms {
+            options = options.set_fieldnorms();
+        }
+
+        options
+    }
+}
+
+/// Facets are always indexed and fast, only storing them is optional.
+impl From<BurkazSchemaFieldOptions> for FacetOptions {
+    fn from(field_options: BurkazSchemaFieldOptions) -> Self {
+        let mut options = FacetOptions::default();
+
+        if field_options.stored {
+            options = options.set_stored();
+        }
+
+        options
+    }
+}
+
 impl Into<TextOptions> for BurkazSchemaFieldOptions {
     fn into(self) -> TextOptions {
         let mut options = TextOptions::default();
@@ -162,6 +385,9 @@ impl Into<TextOptions> for BurkazSchemaFieldOptions {
 
             if let Some(indexing_tokenizer) = self.indexing_tokenizer {
                 text_field_indexing = text_field_indexing.set_tokenizer(&indexing_tokenizer);
+            } else if self.unique {
+                // Keys are matched as a whole, so they must not be split into tokens.
+                text_field_indexing = text_field_indexing.set_tokenizer("raw");
             }
 
             text_field_indexing = text_field_indexing.set_fieldnorms(self.fieldnorms);
@@ -176,3 +402,36 @@ impl Into<TextOptions> for BurkazSchemaFieldOptions {
         options
     }
 }
+
+impl From<BurkazSchemaFieldOptions> for JsonObjectOptions {
+    fn from(field_options: BurkazSchemaFieldOptions) -> Self {
+        let mut options = JsonObjectOptions::default();
+
+        if field_options.stored {
+            options = options.set_stored();
+        }
+
+        if field_options.indexed {
+            let mut text_field_indexing = TextFieldIndexing::default();
+
+            if let Some(indexing_strategy) = field_options.indexing_strategy {
+                text_field_indexing =
+                    text_field_indexing.set_index_option(indexing_strategy.into());
+            }
+
+            if let Some(indexing_tokenizer) = field_options.indexing_tokenizer {
+                text_field_indexing = text_field_indexing.set_tokenizer(&indexing_tokenizer);
+            }
+
+            text_field_indexing = text_field_indexing.set_fieldnorms(field_options.fieldnorms);
+
+            options = options.set_indexing_options(text_field_indexing);
+        }
+
+        if field_options.fast {
+            options = options.set_fast(field_options.fast_tokenizer.as_deref());
+        }
+
+        options
+    }
+}
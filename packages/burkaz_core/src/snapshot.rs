@@ -0,0 +1,137 @@
+use tantivy::{Searcher, TantivyDocument};
+
+use crate::{
+    address::BurkazObjectAddr,
+    index::{get_object, get_object_by_id, get_objects},
+};
+
+/// A fixed view of an index, as it was when the snapshot was taken.
+///
+/// Commits made afterwards are not visible through the snapshot, so paging over
+/// its results and fetching objects by address stays consistent. The segments
+/// it reads are kept alive until the snapshot is dropped.
+#[derive(Clone)]
+pub struct BurkazSnapshot {
+    searcher: Searcher,
+}
+
+impl BurkazSnapshot {
+    #[inline]
+    pub fn new(searcher: Searcher) -> Self {
+        Self { searcher }
+    }
+
+    pub unsafe fn from_raw(ptr: *mut Self) -> Self {
+        unsafe { *Box::from_raw(ptr) }
+    }
+
+    pub fn into_raw(self) -> *mut Self {
+        Box::into_raw(Box::new(self))
+    }
+
+    #[inline]
+    pub fn searcher(&self) -> &Searcher {
+        &self.searcher
+    }
+
+    /// Identifies the view, snapshots taken between the same two commits share
+    /// the same generation.
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.searcher.generation().generation_id()
+    }
+
+    pub fn get(&self, addr: BurkazObjectAddr) -> crate::Result<TantivyDocument> {
+        get_object(&self.searcher, addr)
+    }
+
+    pub fn get_many(
+        &self,
+        addrs: &[BurkazObjectAddr],
+    ) -> crate::Result<Vec<Option<TantivyDocument>>> {
+        get_objects(&self.searcher, addrs)
+    }
+
+    pub fn get_by_id(&self, id: u64) -> crate::Result<TantivyDocument> {
+        get_object_by_id(&self.searcher, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::schema::{Field, Value};
+
+    use super::*;
+    use crate::{
+        error::BurkazError,
+        index::{BurkazDirectory, BurkazIndex},
+        query::BurkazQuery,
+        query_runner::QueryRunner,
+        schema::{
+            BurkazSchema, BurkazSchemaField, BurkazSchemaFieldOptions, BurkazSchemaFieldType,
+        },
+    };
+
+    fn index() -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "position".to_owned(),
+            BurkazSchemaFieldOptions {
+                fast: true,
+                ..BurkazSchemaFieldOptions::for_test(BurkazSchemaFieldType::Int64)
+            },
+        ));
+        BurkazIndex::new("feed".to_owned(), schema, BurkazDirectory::InMemory).unwrap()
+    }
+
+    fn object(position: i64) -> TantivyDocument {
+        let mut object = TantivyDocument::new();
+        object.add_i64(Field::from_field_id(0), position);
+        object
+    }
+
+    fn position(object: &TantivyDocument) -> i64 {
+        object
+            .get_first(Field::from_field_id(0))
+            .and_then(|value| value.as_i64())
+            .unwrap()
+    }
+
+    #[test]
+    fn snapshot_ignores_later_commits() {
+        let index = index();
+        index.add_all((0..4).map(object).collect()).unwrap();
+
+        let snapshot = index.snapshot();
+        let runner =
+            QueryRunner::new(index.downgrade(), BurkazQuery::All).with_snapshot(snapshot.clone());
+        let first_page = runner.search(0, 2).unwrap();
+
+        index.clear().unwrap();
+        index.add_all((10..14).map(object).collect()).unwrap();
+        assert_ne!(index.snapshot().generation(), snapshot.generation());
+
+        let second_page = runner.search(2, 2).unwrap();
+        let mut positions = first_page
+            .into_iter()
+            .chain(second_page)
+            .map(|addr| position(&snapshot.get(addr).unwrap()))
+            .collect::<Vec<_>>();
+        positions.sort();
+        assert_eq!(positions, vec![0, 1, 2, 3]);
+        assert_eq!(runner.count().unwrap(), 4);
+
+        assert_eq!(position(&snapshot.get_by_id(2).unwrap()), 2);
+        assert!(matches!(
+            snapshot.get_by_id(5),
+            Err(BurkazError::ObjectIdNotFound(5))
+        ));
+        assert_eq!(
+            QueryRunner::new(index.downgrade(), BurkazQuery::All)
+                .count()
+                .unwrap(),
+            4
+        );
+        assert_eq!(position(&index.get_by_id(5).unwrap()), 11);
+    }
+}
@@ -0,0 +1,407 @@
+use std::{collections::HashMap, ops::Range};
+
+use levenshtein_automata::{DFA, Distance, LevenshteinAutomatonBuilder, SINK_STATE};
+use tantivy::{
+    Searcher, TantivyError,
+    schema::{Field, FieldType},
+    tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer},
+};
+use tantivy_fst::Automaton;
+
+use crate::{
+    analyzer::AUTOCOMPLETE_ANALYZER_NAME, error::BurkazError, index::BurkazIndex,
+    query_runner::field_entry, schema::ID_FIELD_NAME,
+};
+
+/// Largest edit distance looked up, greater distances are clamped to it.
+pub const MAX_SPELLING_DISTANCE: u8 = 2;
+
+/// Operators of the query syntax, they are never corrected.
+const QUERY_OPERATORS: &[&str] = &["AND", "OR", "NOT", "TO"];
+
+/// An indexed term close to a misspelled word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BurkazSpellingAlternative {
+    pub term: String,
+    /// Number of objects holding the term in the checked fields.
+    pub doc_freq: u64,
+    /// Edit distance between the word and the term, transpositions count as
+    /// one edit.
+    pub distance: u8,
+}
+
+/// A word of the query found in none of the checked fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BurkazWordCorrection {
+    pub word: String,
+    /// Byte range of the word in the query text.
+    pub range: Range<usize>,
+    /// Most frequent terms first, empty when nothing is close enough.
+    pub alternatives: Vec<BurkazSpellingAlternative>,
+}
+
+/// Result of a spell check, see [`BurkazIndex::spell_check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BurkazSpellCorrection {
+    /// The query text with every misspelled word replaced by its first
+    /// alternative, equal to the checked text when nothing was corrected.
+    pub query_text: String,
+    pub corrections: Vec<BurkazWordCorrection>,
+}
+
+impl BurkazSpellCorrection {
+    #[inline]
+    pub fn is_corrected(&self) -> bool {
+        self.corrections
+            .iter()
+            .any(|correction| !correction.alternatives.is_empty())
+    }
+}
+
+impl BurkazIndex {
+    /// Looks up every word of a query text in the term dictionaries of the
+    /// given text fields, all indexed text fields but autocomplete ones when
+    /// none are given, and offers the closest indexed terms for the words
+    /// found in none of them.
+    ///
+    /// Words go through the analyzer of each field before the lookup, so
+    /// alternatives are indexed terms, lowercased or stemmed like the field.
+    /// Field names and operators of the query syntax are left untouched, and
+    /// so are words not longer than the edit distance.
+    pub fn spell_check(
+        &self,
+        query_text: &str,
+        field_ids: &[u32],
+        max_distance: u8,
+        max_alternatives: usize,
+    ) -> crate::Result<BurkazSpellCorrection> {
+        let searcher = self.searcher();
+        let fields = text_fields(&searcher, field_ids)?;
+        let max_distance = max_distance.min(MAX_SPELLING_DISTANCE);
+        let automaton_builder = LevenshteinAutomatonBuilder::new(max_distance, true);
+
+        let mut analyzers = fields
+            .iter()
+            .map(|field| self.underlying_index().tokenizer_for_field(*field))
+            .collect::<tantivy::Result<Vec<_>>>()?;
+
+        let mut corrections = Vec::new();
+        for (word, range) in query_words(query_text) {
+            if word.chars().count() <= max_distance as usize {
+                continue;
+            }
+
+            let mut lookups = Vec::with_capacity(fields.len());
+            for (field, analyzer) in fields.iter().zip(&mut analyzers) {
+                let mut tokens = analyzer.token_stream(&word);
+                let mut normalized = None;
+                while let Some(token) = tokens.next() {
+                    normalized = Some(token.text.clone());
+                }
+                lookups.push((*field, normalized));
+            }
+            // Words removed by an analyzer, like stop words, are never wrong.
+            if lookups.iter().any(|(_, normalized)| normalized.is_none()) {
+                continue;
+            }
+
+            let mut known = false;
+            for (field, normalized) in &lookups {
+                let normalized = normalized.as_deref().unwrap_or_default();
+                if doc_freq(&searcher, *field, normalized)? > 0 {
+                    known = true;
+                    break;
+                }
+            }
+            if known {
+                continue;
+            }
+
+            let mut candidates = HashMap::<String, (u64, u8)>::new();
+            for (field, normalized) in &lookups {
+                let dfa = automaton_builder.build_dfa(normalized.as_deref().unwrap_or_default());
+                for segment_reader in searcher.segment_readers() {
+                    let inverted_index = segment_reader.inverted_index(*field)?;
+                    let mut terms = inverted_index
+                        .terms()
+                        .search(LevenshteinAutomaton(&dfa))
+                        .into_stream()
+                        .map_err(TantivyError::from)?;
+                    while terms.advance() {
+                        let Ok(term) = std::str::from_utf8(terms.key()) else {
+                            continue;
+                        };
+                        let Distance::Exact(distance) = dfa.eval(term) else {
+                            continue;
+                        };
+                        let candidate = candidates.entry(term.to_owned()).or_insert((0, distance));
+                        candidate.0 += u64::from(terms.value().doc_freq);
+                        candidate.1 = candidate.1.min(distance);
+                    }
+                }
+            }
+
+            let mut alternatives = candidates
+                .into_iter()
+                .map(|(term, (doc_freq, distance))| BurkazSpellingAlternative {
+                    term,
+                    doc_freq,
+                    distance,
+                })
+                .collect::<Vec<_>>();
+            alternatives.sort_by(|a, b| {
+                b.doc_freq
+                    .cmp(&a.doc_freq)
+                    .then_with(|| a.distance.cmp(&b.distance))
+                    .then_with(|| a.term.cmp(&b.term))
+            });
+            alternatives.truncate(max_alternatives);
+
+            corrections.push(BurkazWordCorrection {
+                word: query_text[range.clone()].to_owned(),
+                range,
+                alternatives,
+            });
+        }
+
+        let mut corrected_text = String::with_capacity(query_text.len());
+        let mut end = 0;
+        for correction in &corrections {
+            if let Some(alternative) = correction.alternatives.first() {
+                corrected_text.push_str(&query_text[end..correction.range.start]);
+                corrected_text.push_str(&alternative.term);
+                end = correction.range.end;
+            }
+        }
+        corrected_text.push_str(&query_text[end..]);
+
+        Ok(BurkazSpellCorrection {
+            query_text: corrected_text,
+            corrections,
+        })
+    }
+}
+
+struct LevenshteinAutomaton<'a>(&'a DFA);
+
+impl Automaton for LevenshteinAutomaton<'_> {
+    type State = u32;
+
+    fn start(&self) -> u32 {
+        self.0.initial_state()
+    }
+
+    fn is_match(&self, state: &u32) -> bool {
+        matches!(self.0.distance(*state), Distance::Exact(_))
+    }
+
+    fn can_match(&self, state: &u32) -> bool {
+        *state != SINK_STATE
+    }
+
+    fn accept(&self, state: &u32, byte: u8) -> u32 {
+        self.0.transition(*state, byte)
+    }
+}
+
+/// Resolves the checked fields, every indexed text field when none are given.
+/// Fields using the autocomplete analyzer hold word prefixes, which would make
+/// any partial word look correct, so they are never checked.
+fn text_fields(searcher: &Searcher, field_ids: &[u32]) -> crate::Result<Vec<Field>> {
+    let is_indexed_text = |field_type: &FieldType| match field_type {
+        FieldType::Str(options) => options
+            .get_indexing_options()
+            .is_some_and(|indexing| indexing.tokenizer() != AUTOCOMPLETE_ANALYZER_NAME),
+        _ => false,
+    };
+
+    if field_ids.is_empty() {
+        return Ok(searcher
+            .schema()
+            .fields()
+            .filter(|(_, entry)| {
+                entry.name() != ID_FIELD_NAME && is_indexed_text(entry.field_type())
+            })
+            .map(|(field, _)| field)
+            .collect());
+    }
+
+    field_ids
+        .iter()
+        .map(|field_id| {
+            let entry = field_entry(searcher.schema(), *field_id)?;
+            if !is_indexed_text(entry.field_type()) {
+                return Err(BurkazError::InvalidField(format!(
+                    "cannot spell check field `{}`, it is not an indexed text field or it \
+                     uses the autocomplete analyzer",
+                    entry.name()
+                )));
+            }
+            Ok(Field::from_field_id(*field_id))
+        })
+        .collect()
+}
+
+fn doc_freq(searcher: &Searcher, field: Field, term: &str) -> crate::Result<u64> {
+    let mut doc_freq = 0;
+    for segment_reader in searcher.segment_readers() {
+        let inverted_index = segment_reader.inverted_index(field)?;
+        if let Some(term_info) = inverted_index
+            .terms()
+            .get(term.as_bytes())
+            .map_err(TantivyError::from)?
+        {
+            doc_freq += u64::from(term_info.doc_freq);
+        }
+    }
+    Ok(doc_freq)
+}
+
+/// Splits the query text into lowercased words with their byte ranges,
+/// skipping field names and operators.
+fn query_words(query_text: &str) -> Vec<(String, Range<usize>)> {
+    let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+        .filter(LowerCaser)
+        .build();
+    let mut tokens = analyzer.token_stream(query_text);
+    let mut words = Vec::new();
+    while let Some(token) = tokens.next() {
+        let range = token.offset_from..token.offset_to;
+        if query_text[range.end..].starts_with(':')
+            || QUERY_OPERATORS.contains(&&query_text[range.clone()])
+        {
+            continue;
+        }
+        words.push((token.text.clone(), range));
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::TantivyDocument;
+
+    use super::*;
+    use crate::{
+        index::BurkazDirectory,
+        schema::{
+            BurkazSchema, BurkazSchemaField, BurkazSchemaFieldOptions, BurkazSchemaFieldType,
+        },
+    };
+
+    fn titles_index() -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        for (name, typ) in [
+            ("title", BurkazSchemaFieldType::Text),
+            ("stock", BurkazSchemaFieldType::Int64),
+        ] {
+            schema.add_field(BurkazSchemaField::new(
+                name.to_owned(),
+                BurkazSchemaFieldOptions::for_test(typ),
+            ));
+        }
+        let index =
+            BurkazIndex::new("titles".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+        let objects = [
+            "Apple iPhone",
+            "Apple iPad",
+            "Apple pie",
+            "Banana bread",
+            "Maple syrup",
+        ]
+        .into_iter()
+        .map(|title| {
+            let mut object = TantivyDocument::new();
+            object.add_text(Field::from_field_id(0), title);
+            object
+        })
+        .collect();
+        index.add_all(objects).unwrap();
+        index
+    }
+
+    #[test]
+    fn spell_check_rewrites_misspelled_words() {
+        let index = titles_index();
+
+        let correction = index
+            .spell_check("Aple iphne title:banan AND bread", &[], 2, 3)
+            .unwrap();
+        assert_eq!(correction.query_text, "apple iphone title:banana AND bread");
+        assert!(correction.is_corrected());
+
+        let words = correction
+            .corrections
+            .iter()
+            .map(|correction| (correction.word.as_str(), correction.range.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            words,
+            vec![("Aple", 0..4), ("iphne", 5..10), ("banan", 17..22)]
+        );
+
+        let alternatives = correction.corrections[0]
+            .alternatives
+            .iter()
+            .map(|alternative| (alternative.term.as_str(), alternative.doc_freq))
+            .collect::<Vec<_>>();
+        assert_eq!(alternatives, vec![("apple", 3), ("maple", 1), ("pie", 1)]);
+        assert_eq!(correction.corrections[0].alternatives[0].distance, 1);
+
+        let correct = index.spell_check("apple pie", &[0], 1, 3).unwrap();
+        assert_eq!(correct.query_text, "apple pie");
+        assert!(correct.corrections.is_empty());
+
+        let unknown = index.spell_check("zzzzz", &[0], 1, 3).unwrap();
+        assert_eq!(unknown.query_text, "zzzzz");
+        assert!(!unknown.is_corrected());
+
+        assert!(matches!(
+            index.spell_check("apple", &[1], 1, 3),
+            Err(BurkazError::InvalidField(_))
+        ));
+    }
+
+    #[test]
+    fn spell_check_stemmed_fields() {
+        let mut schema = BurkazSchema::default();
+        for (name, tokenizer) in [("title", "english"), ("name", AUTOCOMPLETE_ANALYZER_NAME)] {
+            schema.add_field(BurkazSchemaField::new(
+                name.to_owned(),
+                BurkazSchemaFieldOptions {
+                    indexing_tokenizer: Some(tokenizer.to_owned()),
+                    ..BurkazSchemaFieldOptions::for_test(BurkazSchemaFieldType::Text)
+                },
+            ));
+        }
+        let index =
+            BurkazIndex::new("shoes".to_owned(), schema, BurkazDirectory::InMemory).unwrap();
+        let objects = ["Running shoes", "Runners club"]
+            .into_iter()
+            .map(|title| {
+                let mut object = TantivyDocument::new();
+                object.add_text(Field::from_field_id(0), title);
+                object.add_text(Field::from_field_id(1), title);
+                object
+            })
+            .collect();
+        index.add_all(objects).unwrap();
+
+        // Alternatives are stems, and prefixes indexed for autocomplete like
+        // `runn` do not make a word correct.
+        let correction = index.spell_check("runer shoez runn", &[], 2, 1).unwrap();
+        assert_eq!(correction.query_text, "runner shoe run");
+
+        assert!(
+            index
+                .spell_check("running shoes", &[], 2, 3)
+                .unwrap()
+                .corrections
+                .is_empty()
+        );
+        assert!(matches!(
+            index.spell_check("runn", &[1], 1, 3),
+            Err(BurkazError::InvalidField(_))
+        ));
+    }
+}
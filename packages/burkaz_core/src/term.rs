@@ -12,34 +12,72 @@ pub struct BurkazTerm<B = Vec<u8>>(B)
 where
     B: AsRef<[u8]>;
 
+/// Marks a term addressing a value nested in a JSON field. It is followed by the
+/// length prefixed JSON path and then by the value itself.
+const JSON_PATH_MARKER: u8 = u8::MAX;
+
 impl BurkazTerm<Vec<u8>> {
-    pub fn new(field_id: u32, value: &ReferenceValueLeaf<'_>) -> Self {
+    pub fn new(field_id: u32, value: &ReferenceValueLeaf<'_>) -> crate::Result<Self> {
         let mut bytes = Vec::<u8>::new();
         bytes.write_u32::<LittleEndian>(field_id).unwrap();
+        write_leaf(&mut bytes, value)?;
+        Ok(Self(bytes))
+    }
 
-        macro_rules! write_type {
-            ($type:ident) => {{
-                let _ = u8::serialize(&(crate::object::ValueType::$type as u8), &mut bytes);
-            }};
-        }
+    /// Creates a term matching `value` at `json_path` in a JSON field, where
+    /// nested keys are separated by dots, e.g. `size.width`.
+    pub fn new_json(
+        field_id: u32,
+        json_path: &str,
+        value: &ReferenceValueLeaf<'_>,
+    ) -> crate::Result<Self> {
+        let mut bytes = Vec::<u8>::new();
+        bytes.write_u32::<LittleEndian>(field_id).unwrap();
+        bytes.push(JSON_PATH_MARKER);
+        let _ = Cow::<str>::serialize(&Cow::Borrowed(json_path), &mut bytes);
+        write_leaf(&mut bytes, value)?;
+        Ok(Self(bytes))
+    }
+}
 
-        macro_rules! write_value {
-            ($type:ident, $value_type:ty, $value:expr) => {{
-                write_type!($type);
-                let _ = <$value_type as crate::object::BinarySerializable>::serialize(
-                    $value, &mut bytes,
-                );
-            }};
-        }
+fn write_leaf(bytes: &mut Vec<u8>, value: &ReferenceValueLeaf<'_>) -> crate::Result<()> {
+    macro_rules! write_type {
+        ($type:ident) => {{
+            let _ = u8::serialize(&(crate::object::ValueType::$type as u8), bytes);
+        }};
+    }
+
+    macro_rules! write_value {
+        ($type:ident, $value_type:ty, $value:expr) => {{
+            write_type!($type);
+            let _ = <$value_type as crate::object::BinarySerializable>::serialize($value, bytes);
+        }};
+    }
 
-        match value {
-            ReferenceValueLeaf::I64(value) => write_value!(Int64, i64, value),
-            ReferenceValueLeaf::Bool(value) => write_value!(Boolean, bool, value),
-            ReferenceValueLeaf::Str(value) => write_value!(Text, Cow<str>, &Cow::Borrowed(value)),
-            _ => unimplemented!("Unsupported value: {:?}", value),
+    match value {
+        ReferenceValueLeaf::I64(value) => write_value!(Int64, i64, value),
+        ReferenceValueLeaf::Bool(value) => write_value!(Boolean, bool, value),
+        ReferenceValueLeaf::Str(value) => write_value!(Text, Cow<str>, &Cow::Borrowed(value)),
+        ReferenceValueLeaf::F64(value) => write_value!(F64, f64, value),
+        ReferenceValueLeaf::U64(value) => write_value!(U64, u64, value),
+        ReferenceValueLeaf::Date(value) => {
+            write_value!(Date, i64, &value.into_timestamp_nanos())
+        }
+        ReferenceValueLeaf::Facet(value) => write_value!(Facet, Cow<str>, &Cow::Borrowed(value)),
+        ReferenceValueLeaf::Bytes(value) => {
+            write_type!(Bytes);
+            let _ = u64::serialize(&(value.len() as u64), bytes);
+            bytes.extend_from_slice(value);
+        }
+        _ => {
+            return Err(crate::error::BurkazError::InvalidField(format!(
+                "unsupported term value {:?}",
+                value
+            )));
         }
-        Self(bytes)
     }
+
+    Ok(())
 }
 
 impl<B> BurkazTerm<B>
@@ -63,7 +101,22 @@ where
     }
 
     pub fn val(&self) -> BurkazValueRef<'_> {
-        BurkazValueRef::wrap(&self.0.as_ref()[4..])
+        let offset = match self.json_path() {
+            Some(json_path) => 4 + 1 + 8 + json_path.len(),
+            None => 4,
+        };
+        BurkazValueRef::wrap(&self.0.as_ref()[offset..])
+    }
+
+    /// Returns the path of the value in a JSON field, if the term has one.
+    pub fn json_path(&self) -> Option<&str> {
+        let mut bytes = self.0.as_ref().get(4..)?;
+        if bytes.first() != Some(&JSON_PATH_MARKER) {
+            return None;
+        }
+        bytes = &bytes[1..];
+        let len = bytes.read_u64::<LittleEndian>().ok()? as usize;
+        std::str::from_utf8(bytes.get(..len)?).ok()
     }
 
     #[inline]
@@ -76,7 +129,11 @@ impl<B> BurkazTerm<B>
 where
     B: AsRef<[u8]>,
 {
-    pub fn to_tantivy_term(&self) -> tantivy::schema::Term {
+    pub fn to_tantivy_term(&self) -> crate::Result<tantivy::schema::Term> {
+        if let Some(json_path) = self.json_path() {
+            return self.to_tantivy_json_term(json_path);
+        }
+
         let mut bytes = vec![];
 
         bytes
@@ -108,9 +165,82 @@ where
                 bytes.extend_from_slice(text_val.as_bytes());
             }
             crate::object::ValueType::Boolean => write_fast_value!(Bool, val.as_bool().unwrap()),
-            _ => unimplemented!("Unsupported term value type: {:?}", val_type),
+            crate::object::ValueType::F64 => write_fast_value!(F64, val.as_f64().unwrap()),
+            crate::object::ValueType::U64 => write_fast_value!(U64, val.as_u64().unwrap()),
+            crate::object::ValueType::Date => write_fast_value!(Date, val.as_date().unwrap()),
+            crate::object::ValueType::Bytes => {
+                write_type!(Bytes);
+                bytes.extend_from_slice(val.as_bytes().unwrap());
+            }
+            crate::object::ValueType::Facet => {
+                write_type!(Facet);
+                bytes.extend_from_slice(val.as_facet().unwrap().as_bytes());
+            }
+            _ => {
+                return Err(crate::error::BurkazError::InvalidField(format!(
+                    "unsupported term value type {:?}",
+                    val_type
+                )));
+            }
         }
 
-        tantivy::schema::Term::wrap(bytes)
+        Ok(tantivy::schema::Term::wrap(bytes))
+    }
+
+    fn to_tantivy_json_term(&self, json_path: &str) -> crate::Result<tantivy::schema::Term> {
+        let field = tantivy::schema::Field::from_field_id(self.field_id().unwrap());
+        let mut term = tantivy::schema::Term::from_field_json_path(field, json_path, false);
+
+        let val = self.val();
+        let val_type = val.typ();
+
+        // Mirrors how tantivy indexes JSON values: numbers that fit are stored as
+        // i64 and dates are truncated to the second.
+        match val_type {
+            crate::object::ValueType::Int64 => {
+                term.append_type_and_fast_value(val.as_int().unwrap())
+            }
+            crate::object::ValueType::Text => term.append_type_and_str(val.as_text().unwrap()),
+            crate::object::ValueType::Boolean => {
+                term.append_type_and_fast_value(val.as_bool().unwrap())
+            }
+            crate::object::ValueType::F64 => term.append_type_and_fast_value(val.as_f64().unwrap()),
+            crate::object::ValueType::U64 => {
+                let value = val.as_u64().unwrap();
+                match i64::try_from(value) {
+                    Ok(value) => term.append_type_and_fast_value(value),
+                    Err(_) => term.append_type_and_fast_value(value),
+                }
+            }
+            crate::object::ValueType::Date => term.append_type_and_fast_value(
+                val.as_date()
+                    .unwrap()
+                    .truncate(tantivy::schema::DateTimePrecision::Seconds),
+            ),
+            _ => {
+                return Err(crate::error::BurkazError::InvalidField(format!(
+                    "unsupported JSON term value type {:?}",
+                    val_type
+                )));
+            }
+        }
+
+        Ok(term)
+    }
+
+    /// Like [`BurkazTerm::to_tantivy_term`], but matches the precision used by
+    /// the inverted index, which only keeps dates to the second.
+    pub fn to_tantivy_search_term(&self) -> crate::Result<tantivy::schema::Term> {
+        if self.json_path().is_some() {
+            return self.to_tantivy_term();
+        }
+
+        match self.val().as_date() {
+            Some(date) => Ok(tantivy::schema::Term::from_field_date_for_search(
+                tantivy::schema::Field::from_field_id(self.field_id().unwrap()),
+                date,
+            )),
+            None => self.to_tantivy_term(),
+        }
     }
 }
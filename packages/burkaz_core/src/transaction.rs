@@ -0,0 +1,196 @@
+use tantivy::TantivyDocument;
+
+use crate::{
+    error::BurkazError,
+    index::{BurkazIndex, WeakBurkazIndex},
+    query::BurkazQuery,
+    term::BurkazTerm,
+};
+
+/// A single mutation recorded by a [`BurkazTransaction`].
+pub enum BurkazWriteOperation {
+    /// Adds an object to the index.
+    Add(TantivyDocument),
+    /// Deletes every object matching the query.
+    DeleteByQuery(BurkazQuery),
+    /// Deletes every object in the index.
+    DeleteAll,
+    /// Replaces the object sharing the same key, or adds it if there is none.
+    Upsert(TantivyDocument),
+    /// Deletes the object with the given key.
+    DeleteByKey(BurkazTerm),
+}
+
+/// A batch of mutations that are applied atomically with a single commit.
+///
+/// Operations are buffered until [`BurkazTransaction::commit`] is called, so the
+/// writer lock is only held while the batch is applied. Dropping the transaction
+/// without committing discards every buffered operation.
+pub struct BurkazTransaction {
+    _index: WeakBurkazIndex,
+    operations: Vec<BurkazWriteOperation>,
+}
+
+impl BurkazTransaction {
+    #[inline]
+    pub fn new(index: WeakBurkazIndex) -> Self {
+        Self {
+            _index: index,
+            operations: Vec::new(),
+        }
+    }
+
+    pub unsafe fn from_raw(ptr: *mut Self) -> Self {
+        unsafe { *Box::from_raw(ptr) }
+    }
+
+    pub fn into_raw(self) -> *mut Self {
+        Box::into_raw(Box::new(self))
+    }
+
+    #[inline]
+    fn index(&self) -> Option<BurkazIndex> {
+        self._index.upgrade()
+    }
+
+    pub fn add(&mut self, object: TantivyDocument) {
+        self.operations.push(BurkazWriteOperation::Add(object));
+    }
+
+    pub fn add_all(&mut self, objects: Vec<TantivyDocument>) {
+        self.operations
+            .extend(objects.into_iter().map(BurkazWriteOperation::Add));
+    }
+
+    pub fn delete_all_by_query(&mut self, query: BurkazQuery) {
+        self.operations
+            .push(BurkazWriteOperation::DeleteByQuery(query));
+    }
+
+    pub fn upsert(&mut self, object: TantivyDocument) {
+        self.operations.push(BurkazWriteOperation::Upsert(object));
+    }
+
+    pub fn delete_by_key(&mut self, key: BurkazTerm) {
+        self.operations.push(BurkazWriteOperation::DeleteByKey(key));
+    }
+
+    pub fn clear(&mut self) {
+        self.operations.push(BurkazWriteOperation::DeleteAll);
+    }
+
+    /// Applies every buffered operation and commits them at once. Returns the
+    /// stable ids given to the added and upserted objects, in order.
+    pub fn commit(self) -> crate::Result<Vec<u64>> {
+        let index = self.index().ok_or(BurkazError::IndexClosed)?;
+        index.apply(self.operations)
+    }
+
+    /// Discards every buffered operation.
+    #[inline]
+    pub fn rollback(self) {
+        drop(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::schema::Field;
+
+    use super::*;
+    use crate::{
+        index::BurkazDirectory,
+        query_runner::QueryRunner,
+        schema::{
+            BurkazSchema, BurkazSchemaField, BurkazSchemaFieldOptions, BurkazSchemaFieldType,
+        },
+    };
+
+    fn index() -> BurkazIndex {
+        let mut schema = BurkazSchema::default();
+        schema.add_field(BurkazSchemaField::new(
+            "id".to_owned(),
+            BurkazSchemaFieldOptions::for_test(BurkazSchemaFieldType::Int64),
+        ));
+        BurkazIndex::new("transactions".to_owned(), schema, BurkazDirectory::InMemory).unwrap()
+    }
+
+    fn object(id: i64) -> TantivyDocument {
+        let mut object = TantivyDocument::new();
+        object.add_i64(Field::from_field_id(0), id);
+        object
+    }
+
+    fn count(index: &BurkazIndex) -> usize {
+        QueryRunner::new(index.downgrade(), BurkazQuery::All)
+            .count()
+            .unwrap()
+    }
+
+    #[test]
+    fn commit_applies_all_operations() {
+        let index = index();
+        index.add_all((0..3).map(object).collect()).unwrap();
+
+        let mut transaction = index.begin_transaction();
+        transaction.clear();
+        transaction.add_all((0..10).map(object).collect());
+        transaction.add(object(10));
+        assert_eq!(count(&index), 3);
+
+        transaction.commit().unwrap();
+        assert_eq!(count(&index), 11);
+    }
+
+    #[test]
+    fn clear_drops_buffered_operations() {
+        let index = index();
+
+        let mut transaction = index.begin_transaction();
+        transaction.add(object(0));
+        transaction.clear();
+        let ids = transaction.commit().unwrap();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(count(&index), 0);
+
+        let mut transaction = index.begin_transaction();
+        transaction.add(object(1));
+        transaction.clear();
+        transaction.add(object(2));
+        let ids = transaction.commit().unwrap();
+        assert_eq!(count(&index), 1);
+        assert_eq!(
+            index
+                .get_by_id(ids[1])
+                .unwrap()
+                .get_first(Field::from_field_id(0))
+                .and_then(|value| tantivy::schema::Value::as_i64(&value)),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn rollback_discards_operations() {
+        let index = index();
+
+        let mut transaction = index.begin_transaction();
+        transaction.add(object(0));
+        transaction.rollback();
+
+        assert_eq!(count(&index), 0);
+    }
+
+    #[test]
+    fn commit_fails_after_index_is_closed() {
+        let index = index();
+
+        let mut transaction = index.begin_transaction();
+        transaction.add(object(0));
+        drop(index);
+
+        assert!(matches!(
+            transaction.commit(),
+            Err(BurkazError::IndexClosed)
+        ));
+    }
+}